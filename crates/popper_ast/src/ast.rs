@@ -1,11 +1,13 @@
-use crate::attribute::Attribute;
+use crate::attribute::{Attribute, ModuleAttributes};
 use crate::layer::{Ast, Layer};
 use crate::token::TokenKind;
 use crate::type_::Type;
+use crate::visit::Visitor;
 use popper_index::Idx;
 use std::fmt::Write;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span {
     pub lo: usize,
     pub hi: usize,
@@ -30,6 +32,7 @@ impl From<(usize, usize)> for Span {
     }
 }
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LangNodeId(pub u32);
 
 impl Idx for LangNodeId {
@@ -45,6 +48,7 @@ impl Idx for LangNodeId {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SymbolId(u32);
 
 impl Idx for SymbolId {
@@ -59,14 +63,17 @@ impl Idx for SymbolId {
     }
 }
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ident(pub SymbolId);
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Symbol {
     pub name: String,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SymbolTable {
     symbols: Vec<Symbol>,
 }
@@ -98,9 +105,12 @@ impl SymbolTable {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LangAst {
     pub root: LangNodeId,
     pub nodes: Vec<LangNode>,
+    /// `None` if the module has no `module(..);` statement.
+    pub module_attrs: Option<ModuleAttributes>,
     symbol_table: SymbolTable,
 }
 
@@ -109,6 +119,7 @@ impl LangAst {
         LangAst {
             root: LangNodeId(0),
             nodes: Vec::new(),
+            module_attrs: None,
             symbol_table: SymbolTable::new(),
         }
     }
@@ -134,132 +145,186 @@ impl LangAst {
     }
 
     pub fn dumps(&self) -> String {
-        let node = self.get(self.root);
-        self.dumps_node(node)
-    }
-
-    fn dumps_node(&self, node: &LangNode) -> String {
-        match &node.kind {
-            LangNodeKind::Expr(expr) => self.dumps_expr(expr),
-            LangNodeKind::Let(let_) => format!(
-                "Let {} = {}",
-                self.dumps_symbol(let_.name.0),
-                self.dumps_node(self.get(let_.value))
-            ),
-            LangNodeKind::Block(nodes) => {
-                let mut result = String::new();
-                for &id in nodes {
-                    result.push_str(&self.dumps_node(self.get(id)));
-                    result.push('\n');
-                }
-                result
-            }
-            LangNodeKind::If {
-                condition,
-                then_branch,
-                else_branch,
-            } => {
-                let mut result = format!(
-                    "If {} then {} ",
-                    self.dumps_node(self.get(*condition)),
-                    self.dumps_node(self.get(*then_branch))
-                );
-                if let Some(else_id) = else_branch {
-                    result.push_str(&format!("else {}", self.dumps_node(self.get(*else_id))));
-                }
-                result
-            }
-            LangNodeKind::FunctionCall { function, args } => {
-                let func_str = self.dumps_node(self.get(*function));
-                let args_str: Vec<String> = args
-                    .iter()
-                    .map(|&id| self.dumps_node(self.get(id)))
-                    .collect();
-                format!("{}.call([{}])", func_str, args_str.join(", "))
-            }
-            LangNodeKind::Return(expr) => format!("Return({})", self.dumps_node(self.get(*expr))),
-            LangNodeKind::FunctionDef {
-                name,
-                attrs,
-                params,
-                ret,
-                body,
-                is_expr,
-            } => {
-                let params_str: Vec<String> = params
-                    .iter()
-                    .map(|param| format!("{}: {:?}", param.name.0.index(), param.ty))
-                    .collect();
-                let attr_str: Vec<String> = attrs.iter().map(|x| self.dump_attribute(*x)).collect();
-                let s = if let Some(body) = body {
-                    if *is_expr {
-                        format!("= {}", self.dumps_node(self.get(*body)))
-                    } else {
-                        format!("{}", self.dumps_node(self.get(*body)))
-                    }
-                } else {
-                    ";".to_string()
-                };
-                format!(
-                    "FunctionDef({}) {}({}) -> {:?} {}",
-                    attr_str.join(","),
-                    self.dumps_symbol(name.0),
-                    params_str.join(", "),
-                    ret,
-                    s
-                )
-            }
+        let mut dumper = Dumper { out: String::new() };
+        if let Some(module_attrs) = &self.module_attrs {
+            let attr_str: Vec<String> = module_attrs.attrs.iter().map(|x| self.dump_attribute(x)).collect();
+            let _ = writeln!(dumper.out, "Module({})", attr_str.join(","));
         }
+        dumper.visit_node(self, self.root);
+        dumper.out
     }
 
-    fn dump_attribute(&self, attribute: Attribute) -> String {
+    fn dumps_visibility(&self, visibility: Visibility) -> &'static str {
+        match visibility {
+            Visibility::Public => "pub ",
+            Visibility::Private => "",
+        }
+    }
+
+    fn dump_attribute(&self, attribute: &Attribute) -> String {
         match attribute {
             Attribute::StdCallC => "C".to_string(),
+            Attribute::Lint(level, lints) => format!("{}({})", level.name(), lints.join(", ")),
         }
     }
 
-    fn dumps_expr(&self, expr: &Expr) -> String {
+    fn dumps_symbol(&self, id: SymbolId) -> String {
+        self.symbol_table.get(id).name.clone()
+    }
+}
+
+/// Writes the nodes it visits in the format of `LangAst::dumps`.
+struct Dumper {
+    out: String,
+}
+
+impl Dumper {
+    fn binary(&mut self, ast: &LangAst, name: &str, lhs: LangNodeId, rhs: LangNodeId) {
+        let _ = write!(self.out, "{name}(");
+        self.visit_node(ast, lhs);
+        self.out.push_str(", ");
+        self.visit_node(ast, rhs);
+        self.out.push(')');
+    }
+}
+
+impl<'ast> Visitor<'ast> for Dumper {
+    fn visit_expr(&mut self, ast: &'ast LangAst, _id: LangNodeId, expr: &'ast Expr) {
         match expr {
-            Expr::Ident(ident) => format!("Ident({})", self.dumps_symbol(ident.0)),
-            Expr::Int(value) => format!("Int({})", value),
-            Expr::String(value) => format!("String({})", value),
+            Expr::Ident(ident) => {
+                let _ = write!(self.out, "Ident({})", ast.dumps_symbol(ident.0));
+            }
+            Expr::Int(value) => {
+                let _ = write!(self.out, "Int({value})");
+            }
+            Expr::String(value) => {
+                let _ = write!(self.out, "String({value})");
+            }
+            Expr::Char(value) => {
+                let _ = write!(self.out, "Char({value:?})");
+            }
             Expr::UnaryOp(op, node_id) => {
                 let op_str = match op {
                     UnaryOpKind::Negate => "!",
                     UnaryOpKind::ArithmeticNegate => "-",
                     UnaryOpKind::ArithmeticPlus => "+",
                 };
-                format!(
-                    "UnaryOp({}, {})",
-                    op_str,
-                    self.dumps_node(self.get(*node_id))
-                )
+                let _ = write!(self.out, "UnaryOp({op_str}, ");
+                self.visit_node(ast, *node_id);
+                self.out.push(')');
+            }
+            Expr::Add(lhs, rhs) => self.binary(ast, "Add", *lhs, *rhs),
+            Expr::Sub(lhs, rhs) => self.binary(ast, "Sub", *lhs, *rhs),
+            Expr::Mul(lhs, rhs) => self.binary(ast, "Mul", *lhs, *rhs),
+            Expr::Div(lhs, rhs) => self.binary(ast, "Div", *lhs, *rhs),
+            Expr::Cast(value, ty) => {
+                self.out.push_str("Cast(");
+                self.visit_node(ast, *value);
+                let _ = write!(self.out, ", {ty})");
             }
-            Expr::Add(lhs, rhs) => format!(
-                "Add({}, {})",
-                self.dumps_node(self.get(*lhs)),
-                self.dumps_node(self.get(*rhs))
-            ),
-            Expr::Sub(lhs, rhs) => format!(
-                "Sub({}, {})",
-                self.dumps_node(self.get(*lhs)),
-                self.dumps_node(self.get(*rhs))
-            ),
-            Expr::Mul(lhs, rhs) => format!(
-                "Mul({}, {})",
-                self.dumps_node(self.get(*lhs)),
-                self.dumps_node(self.get(*rhs))
-            ),
-            Expr::Div(lhs, rhs) => format!(
-                "Div({}, {})",
-                self.dumps_node(self.get(*lhs)),
-                self.dumps_node(self.get(*rhs))
-            ),
         }
     }
 
-    fn dumps_symbol(&self, id: SymbolId) -> String {
-        self.symbol_table.get(id).name.clone()
+    fn visit_let(&mut self, ast: &'ast LangAst, _id: LangNodeId, let_: &'ast Let) {
+        let _ = write!(
+            self.out,
+            "Let {}{} = ",
+            ast.dumps_visibility(let_.visibility),
+            ast.dumps_symbol(let_.name.0)
+        );
+        self.visit_node(ast, let_.value);
+    }
+
+    fn visit_block(&mut self, ast: &'ast LangAst, _id: LangNodeId, items: &'ast [LangNodeId]) {
+        for &id in items {
+            self.visit_node(ast, id);
+            self.out.push('\n');
+        }
+    }
+
+    fn visit_if(
+        &mut self,
+        ast: &'ast LangAst,
+        _id: LangNodeId,
+        condition: LangNodeId,
+        then_branch: LangNodeId,
+        else_branch: Option<LangNodeId>,
+    ) {
+        self.out.push_str("If ");
+        self.visit_node(ast, condition);
+        self.out.push_str(" then ");
+        self.visit_node(ast, then_branch);
+        self.out.push(' ');
+        if let Some(else_id) = else_branch {
+            self.out.push_str("else ");
+            self.visit_node(ast, else_id);
+        }
+    }
+
+    fn visit_function_call(&mut self, ast: &'ast LangAst, _id: LangNodeId, function: LangNodeId, args: &'ast [LangNodeId]) {
+        self.visit_node(ast, function);
+        self.out.push_str(".call([");
+        for (i, &arg) in args.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            self.visit_node(ast, arg);
+        }
+        self.out.push_str("])");
+    }
+
+    fn visit_return(&mut self, ast: &'ast LangAst, _id: LangNodeId, value: LangNodeId) {
+        self.out.push_str("Return(");
+        self.visit_node(ast, value);
+        self.out.push(')');
+    }
+
+    fn visit_function_def(
+        &mut self,
+        ast: &'ast LangAst,
+        id: LangNodeId,
+        params: &'ast [ArgumentParamDef],
+        body: Option<LangNodeId>,
+    ) {
+        let LangNodeKind::FunctionDef {
+            name,
+            visibility,
+            attrs,
+            ret,
+            is_expr,
+            ..
+        } = &ast.get(id).kind
+        else {
+            return;
+        };
+        let params_str: Vec<String> = params
+            .iter()
+            .map(|param| format!("{}: {:?}", param.name.0.index(), param.ty))
+            .collect();
+        let attr_str: Vec<String> = attrs.iter().map(|x| ast.dump_attribute(x)).collect();
+        let _ = write!(
+            self.out,
+            "FunctionDef({}) {}{}({}) -> {:?} ",
+            attr_str.join(","),
+            ast.dumps_visibility(*visibility),
+            ast.dumps_symbol(name.0),
+            params_str.join(", "),
+            ret
+        );
+        match body {
+            Some(body) => {
+                if *is_expr {
+                    self.out.push_str("= ");
+                }
+                self.visit_node(ast, body);
+            }
+            None => self.out.push(';'),
+        }
+    }
+
+    fn visit_import(&mut self, ast: &'ast LangAst, _id: LangNodeId, path: &'ast [Ident]) {
+        let path_str: Vec<String> = path.iter().map(|x| ast.dumps_symbol(x.0)).collect();
+        let _ = write!(self.out, "Import({})", path_str.join("."));
     }
 }
 
@@ -291,12 +356,14 @@ impl Ast for LangAst {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LangNode {
     pub kind: LangNodeKind,
     pub span: Span,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LangNodeKind {
     Expr(Expr),
     Let(Let),
@@ -313,21 +380,28 @@ pub enum LangNodeKind {
     Return(LangNodeId),
     FunctionDef {
         name: Ident,
+        name_span: Span,
+        visibility: Visibility,
         attrs: Vec<Attribute>,
         params: Vec<ArgumentParamDef>,
         ret: Type,
         body: Option<LangNodeId>,
         is_expr: bool,
     },
+    Import(Vec<Ident>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArgumentParamDef {
     pub name: Ident,
     pub ty: Type,
+    pub span: Span,
+    pub name_span: Span,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOpKind {
     Negate,           // Negation (logical negation)
     ArithmeticPlus,   // Arithmetic plus (+3)
@@ -335,23 +409,38 @@ pub enum UnaryOpKind {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expr {
     Ident(Ident),
     Int(i64),
     String(String),
+    Char(char),
     UnaryOp(UnaryOpKind, LangNodeId),
     Add(LangNodeId, LangNodeId),
     Sub(LangNodeId, LangNodeId),
     Mul(LangNodeId, LangNodeId),
     Div(LangNodeId, LangNodeId),
+    Cast(LangNodeId, Type),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Let {
     pub name: Ident,
+    pub name_span: Span,
+    pub visibility: Visibility,
     pub value: LangNodeId,
 }
 
+/// Whether a top-level item can be used from other modules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Visibility {
+    Public,
+    #[default]
+    Private,
+}
+
 #[derive(Debug, Clone)]
 pub struct LineInfo {
     pub line: usize,
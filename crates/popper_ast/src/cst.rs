@@ -0,0 +1,232 @@
+use crate::ast::{Expr, LangAst, LangNode, LangNodeId, LangNodeKind};
+use crate::layer::Ast;
+use crate::token::{Token, TokenKind};
+use crate::visit::children;
+use popper_index::Idx;
+
+/// A token along with the whitespace, newlines and comments around it, its trivia. The
+/// trailing trivia runs up to the end of the line of the token, newline included, and
+/// the leading trivia holds the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CstToken {
+    pub leading: Vec<Token>,
+    pub token: Token,
+    pub trailing: Vec<Token>,
+}
+
+impl CstToken {
+    pub fn new(token: Token) -> CstToken {
+        CstToken {
+            leading: Vec::new(),
+            token,
+            trailing: Vec::new(),
+        }
+    }
+
+    /// Whether the trailing trivia reaches the end of the line.
+    pub fn ends_line(&self) -> bool {
+        self.trailing.iter().any(|trivia| trivia.kind == TokenKind::Newline)
+    }
+
+    /// Appends the text of the token, with its trivia, to `out`.
+    pub fn write(&self, out: &mut String) {
+        for trivia in &self.leading {
+            out.push_str(&trivia.value);
+        }
+        out.push_str(&self.token.value);
+        for trivia in &self.trailing {
+            out.push_str(&trivia.value);
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CstNodeId(pub u32);
+
+impl From<LangNodeId> for CstNodeId {
+    fn from(node_id: LangNodeId) -> Self {
+        CstNodeId::new(node_id.index())
+    }
+}
+
+impl Idx for CstNodeId {
+    const MAX_ID: usize = u32::MAX as usize;
+    const MAX: Self = CstNodeId(u32::MAX);
+
+    fn new(val: usize) -> Self {
+        CstNodeId(u32::new(val))
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A child of a CST node: the node of a sub-expression or sub-statement, or a token of
+/// the node itself, such as a keyword or a `;`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CstElement {
+    Node(CstNodeId),
+    Token(CstToken),
+}
+
+#[derive(Debug, Clone)]
+pub struct CstNode {
+    pub node: LangNode,
+    /// The children in source order.
+    pub children: Vec<CstElement>,
+}
+
+/// The concrete syntax tree of a source file: the nodes of its AST, under the same ids,
+/// holding every token of the source with its trivia. The text of the root is the
+/// source, byte for byte.
+#[derive(Debug, Clone)]
+pub struct Cst {
+    root: CstNodeId,
+    nodes: Vec<CstNode>,
+}
+
+impl Cst {
+    /// Builds the CST of the source `ast` was parsed from, out of the `tokens` of the
+    /// source as lexed in lossless mode, ending with `Eof`.
+    pub fn create_from_ast(ast: &LangAst, tokens: Vec<CstToken>) -> Cst {
+        let mut cst = Cst {
+            root: ast.root.into(),
+            nodes: ast
+                .nodes
+                .iter()
+                .map(|node| CstNode {
+                    node: node.clone(),
+                    children: Vec::new(),
+                })
+                .collect(),
+        };
+        let mut tokens = tokens.into_iter().enumerate().peekable();
+        cst.fill(ast, ast.root, usize::MAX, &mut tokens);
+        // The root only ends at the end of file, which holds the trivia after the last
+        // token.
+        let root = cst.root.index();
+        cst.nodes[root].children.extend(tokens.map(|(_, token)| CstElement::Token(token)));
+        cst
+    }
+
+    /// Moves the tokens of `id`, the ones ending before `end`, from `tokens` into the
+    /// children of `id`, recursing into the children of its AST node as their first
+    /// token comes.
+    fn fill(
+        &mut self,
+        ast: &LangAst,
+        id: LangNodeId,
+        end: usize,
+        tokens: &mut std::iter::Peekable<impl Iterator<Item = (usize, CstToken)>>,
+    ) {
+        let mut children = children(ast, id);
+        children.sort_by_key(|&child| start(ast, child));
+        let mut children = children.into_iter().peekable();
+        let mut elements = Vec::new();
+
+        while let Some((_, token)) = tokens.peek() {
+            let span = token.token.span;
+            if token.token.kind == TokenKind::Eof || span.hi > end {
+                break;
+            }
+            // A child starting before the current token overlaps a previous one, its
+            // tokens are left to the parent.
+            while children.next_if(|&child| start(ast, child) < span.lo).is_some() {}
+            match children.next_if(|&child| start(ast, child) == span.lo) {
+                Some(child) => {
+                    self.fill(ast, child, self::end(ast, child), tokens);
+                    elements.push(CstElement::Node(child.into()));
+                }
+                None => elements.push(CstElement::Token(tokens.next().unwrap().1)),
+            }
+        }
+        self.nodes[id.index()].children = elements;
+    }
+
+    /// The source text of `id` with the trivia of its tokens.
+    pub fn text(&self, id: CstNodeId) -> String {
+        let mut out = String::new();
+        self.write(id, &mut out);
+        out
+    }
+
+    fn write(&self, id: CstNodeId, out: &mut String) {
+        for child in &self.nodes[id.index()].children {
+            match child {
+                CstElement::Node(child) => self.write(*child, out),
+                CstElement::Token(token) => token.write(out),
+            }
+        }
+    }
+
+    /// The tokens of `id` in source order, including the ones of its descendants.
+    pub fn tokens(&self, id: CstNodeId) -> Vec<&CstToken> {
+        let mut tokens = Vec::new();
+        for child in &self.nodes[id.index()].children {
+            match child {
+                CstElement::Node(child) => tokens.extend(self.tokens(*child)),
+                CstElement::Token(token) => tokens.push(token),
+            }
+        }
+        tokens
+    }
+}
+
+impl Ast for Cst {
+    type NodeId = CstNodeId;
+    type Node = CstNode;
+
+    fn add(&mut self, node: Self::Node) -> Self::NodeId {
+        let id = CstNodeId::new(self.nodes.len());
+        self.nodes.push(node);
+        id
+    }
+
+    fn get(&self, id: Self::NodeId) -> &CstNode {
+        &self.nodes[id.index()]
+    }
+
+    fn get_mut(&mut self, id: Self::NodeId) -> &mut CstNode {
+        &mut self.nodes[id.index()]
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = Self::NodeId> {
+        (0..self.nodes.len()).map(CstNodeId::new)
+    }
+
+    fn root(&self) -> Self::NodeId {
+        self.root
+    }
+}
+
+/// The offset of the first token of `id`. The span of a binary expression starts at its
+/// operator.
+fn start(ast: &LangAst, id: LangNodeId) -> usize {
+    let node = ast.get(id);
+    match &node.kind {
+        LangNodeKind::Expr(
+            Expr::Add(lhs, _) | Expr::Sub(lhs, _) | Expr::Mul(lhs, _) | Expr::Div(lhs, _) | Expr::Cast(lhs, _),
+        ) => start(ast, *lhs),
+        LangNodeKind::FunctionCall { function, .. } => start(ast, *function),
+        _ => node.span.lo,
+    }
+}
+
+/// The end of the last token of `id`, or past it. The span of a binary or unary
+/// expression runs up to the token following it, and the span of a function with an
+/// expression body may cover the blanks after it.
+fn end(ast: &LangAst, id: LangNodeId) -> usize {
+    let node = ast.get(id);
+    match &node.kind {
+        LangNodeKind::Expr(
+            Expr::Add(_, value) | Expr::Sub(_, value) | Expr::Mul(_, value) | Expr::Div(_, value) | Expr::UnaryOp(_, value),
+        ) => end(ast, *value),
+        LangNodeKind::FunctionDef {
+            body: Some(body),
+            is_expr: true,
+            ..
+        } => end(ast, *body),
+        _ => node.span.hi,
+    }
+}
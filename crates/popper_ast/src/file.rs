@@ -2,7 +2,7 @@ use crate::ast::LangAst;
 use popper_index::Idx;
 use std::path::Path;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FileId(u32);
 
 impl Idx for FileId {
@@ -28,9 +28,21 @@ impl File {
     pub fn new(id: FileId, info: SourceFileInfo, ast: Option<LangAst>) -> File {
         File { id, info, ast }
     }
+    pub fn id(&self) -> FileId {
+        self.id
+    }
+
     pub fn info(&self) -> &SourceFileInfo {
         &self.info
     }
+
+    pub fn ast(&self) -> Option<&LangAst> {
+        self.ast.as_ref()
+    }
+
+    pub fn set_ast(&mut self, ast: LangAst) {
+        self.ast = Some(ast);
+    }
 }
 
 #[derive(Clone)]
@@ -57,6 +69,14 @@ impl FileTable {
     pub fn get(&self, id: FileId) -> Option<&File> {
         self.files.get(id.index())
     }
+
+    pub fn get_mut(&mut self, id: FileId) -> Option<&mut File> {
+        self.files.get_mut(id.index())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &File> {
+        self.files.iter()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -70,18 +90,26 @@ pub struct SourceFileInfo {
 
 impl SourceFileInfo {
     pub fn from_file(raw_path: &str) -> Option<SourceFileInfo> {
+        let source = std::fs::read_to_string(raw_path).ok()?;
+        Some(SourceFileInfo::from_source(raw_path, source))
+    }
+
+    /// A file at `raw_path` with the given contents, which may differ from the ones on
+    /// the disk, e.g. for the unsaved buffer of an editor.
+    pub fn from_source(raw_path: &str, source: String) -> SourceFileInfo {
         let path = Path::new(raw_path);
-        let name = path.file_name().unwrap().to_str().unwrap().to_string();
-        let source = std::fs::read_to_string(path).ok()?;
+        let name = path
+            .file_name()
+            .map_or_else(|| raw_path.to_string(), |name| name.to_string_lossy().into_owned());
         let hash = crc::Crc::<u64>::new(&crc::CRC_64_MS).checksum(source.as_bytes());
 
-        Some(SourceFileInfo {
+        SourceFileInfo {
             name,
             source,
             hash,
             path: raw_path.to_string(),
-            absolute_path: path.to_str()?.to_string(),
-        })
+            absolute_path: raw_path.to_string(),
+        }
     }
 
     pub fn new(
@@ -119,4 +147,12 @@ impl SourceFileInfo {
     pub fn absolute_path(&self) -> &str {
         &self.absolute_path
     }
+
+    /// The 1-based line and column (in characters) of the byte `offset`.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let before = &self.source[..offset.min(self.source.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, before[line_start..].chars().count() + 1)
+    }
 }
@@ -0,0 +1,149 @@
+//! Rewriting of a `LangAst`.
+//!
+//! Every `fold_*` method of `Folder` defaults to the `walk_*` function of the same name,
+//! which folds the children of the node and stores the ids they are replaced with in
+//! the node. A method returns the id replacing the node: the node itself, once updated
+//! in place, or a node added to the arena.
+
+use crate::ast::{Expr, Ident, LangAst, LangNodeId, LangNodeKind, Let};
+
+pub trait Folder: Sized {
+    fn fold_node(&mut self, ast: &mut LangAst, id: LangNodeId) -> LangNodeId {
+        walk_node(self, ast, id)
+    }
+
+    fn fold_expr(&mut self, ast: &mut LangAst, id: LangNodeId, expr: Expr) -> LangNodeId {
+        walk_expr(self, ast, id, expr)
+    }
+
+    fn fold_let(&mut self, ast: &mut LangAst, id: LangNodeId, let_: Let) -> LangNodeId {
+        walk_let(self, ast, id, let_)
+    }
+
+    fn fold_block(&mut self, ast: &mut LangAst, id: LangNodeId, items: Vec<LangNodeId>) -> LangNodeId {
+        walk_block(self, ast, id, items)
+    }
+
+    fn fold_if(
+        &mut self,
+        ast: &mut LangAst,
+        id: LangNodeId,
+        condition: LangNodeId,
+        then_branch: LangNodeId,
+        else_branch: Option<LangNodeId>,
+    ) -> LangNodeId {
+        walk_if(self, ast, id, condition, then_branch, else_branch)
+    }
+
+    fn fold_function_call(&mut self, ast: &mut LangAst, id: LangNodeId, function: LangNodeId, args: Vec<LangNodeId>) -> LangNodeId {
+        walk_function_call(self, ast, id, function, args)
+    }
+
+    fn fold_return(&mut self, ast: &mut LangAst, id: LangNodeId, value: LangNodeId) -> LangNodeId {
+        walk_return(self, ast, id, value)
+    }
+
+    /// Folds a function definition. The fields other than the body, such as its name or
+    /// parameters, are in `ast.get(id)`.
+    fn fold_function_def(&mut self, ast: &mut LangAst, id: LangNodeId, body: Option<LangNodeId>) -> LangNodeId {
+        walk_function_def(self, ast, id, body)
+    }
+
+    fn fold_import(&mut self, ast: &mut LangAst, id: LangNodeId, path: Vec<Ident>) -> LangNodeId {
+        walk_import(self, ast, id, path)
+    }
+}
+
+/// Calls the `fold_*` method of the kind of `id`.
+pub fn walk_node<F: Folder>(folder: &mut F, ast: &mut LangAst, id: LangNodeId) -> LangNodeId {
+    match ast.get(id).kind.clone() {
+        LangNodeKind::Expr(expr) => folder.fold_expr(ast, id, expr),
+        LangNodeKind::Let(let_) => folder.fold_let(ast, id, let_),
+        LangNodeKind::Block(items) => folder.fold_block(ast, id, items),
+        LangNodeKind::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => folder.fold_if(ast, id, condition, then_branch, else_branch),
+        LangNodeKind::FunctionCall { function, args } => folder.fold_function_call(ast, id, function, args),
+        LangNodeKind::Return(value) => folder.fold_return(ast, id, value),
+        LangNodeKind::FunctionDef { body, .. } => folder.fold_function_def(ast, id, body),
+        LangNodeKind::Import(path) => folder.fold_import(ast, id, path),
+    }
+}
+
+pub fn walk_expr<F: Folder>(folder: &mut F, ast: &mut LangAst, id: LangNodeId, expr: Expr) -> LangNodeId {
+    let expr = match expr {
+        Expr::Ident(_) | Expr::Int(_) | Expr::String(_) | Expr::Char(_) => expr,
+        Expr::UnaryOp(op, value) => Expr::UnaryOp(op, folder.fold_node(ast, value)),
+        Expr::Cast(value, ty) => Expr::Cast(folder.fold_node(ast, value), ty),
+        Expr::Add(lhs, rhs) => Expr::Add(folder.fold_node(ast, lhs), folder.fold_node(ast, rhs)),
+        Expr::Sub(lhs, rhs) => Expr::Sub(folder.fold_node(ast, lhs), folder.fold_node(ast, rhs)),
+        Expr::Mul(lhs, rhs) => Expr::Mul(folder.fold_node(ast, lhs), folder.fold_node(ast, rhs)),
+        Expr::Div(lhs, rhs) => Expr::Div(folder.fold_node(ast, lhs), folder.fold_node(ast, rhs)),
+    };
+    ast.get_mut(id).kind = LangNodeKind::Expr(expr);
+    id
+}
+
+pub fn walk_let<F: Folder>(folder: &mut F, ast: &mut LangAst, id: LangNodeId, let_: Let) -> LangNodeId {
+    let value = folder.fold_node(ast, let_.value);
+    ast.get_mut(id).kind = LangNodeKind::Let(Let { value, ..let_ });
+    id
+}
+
+pub fn walk_block<F: Folder>(folder: &mut F, ast: &mut LangAst, id: LangNodeId, items: Vec<LangNodeId>) -> LangNodeId {
+    let items = items.into_iter().map(|item| folder.fold_node(ast, item)).collect();
+    ast.get_mut(id).kind = LangNodeKind::Block(items);
+    id
+}
+
+pub fn walk_if<F: Folder>(
+    folder: &mut F,
+    ast: &mut LangAst,
+    id: LangNodeId,
+    condition: LangNodeId,
+    then_branch: LangNodeId,
+    else_branch: Option<LangNodeId>,
+) -> LangNodeId {
+    let condition = folder.fold_node(ast, condition);
+    let then_branch = folder.fold_node(ast, then_branch);
+    let else_branch = else_branch.map(|else_branch| folder.fold_node(ast, else_branch));
+    ast.get_mut(id).kind = LangNodeKind::If {
+        condition,
+        then_branch,
+        else_branch,
+    };
+    id
+}
+
+pub fn walk_function_call<F: Folder>(
+    folder: &mut F,
+    ast: &mut LangAst,
+    id: LangNodeId,
+    function: LangNodeId,
+    args: Vec<LangNodeId>,
+) -> LangNodeId {
+    let function = folder.fold_node(ast, function);
+    let args = args.into_iter().map(|arg| folder.fold_node(ast, arg)).collect();
+    ast.get_mut(id).kind = LangNodeKind::FunctionCall { function, args };
+    id
+}
+
+pub fn walk_return<F: Folder>(folder: &mut F, ast: &mut LangAst, id: LangNodeId, value: LangNodeId) -> LangNodeId {
+    let value = folder.fold_node(ast, value);
+    ast.get_mut(id).kind = LangNodeKind::Return(value);
+    id
+}
+
+pub fn walk_function_def<F: Folder>(folder: &mut F, ast: &mut LangAst, id: LangNodeId, body: Option<LangNodeId>) -> LangNodeId {
+    let folded = body.map(|body| folder.fold_node(ast, body));
+    if let LangNodeKind::FunctionDef { body, .. } = &mut ast.get_mut(id).kind {
+        *body = folded;
+    }
+    id
+}
+
+pub fn walk_import<F: Folder>(_folder: &mut F, _ast: &mut LangAst, id: LangNodeId, _path: Vec<Ident>) -> LangNodeId {
+    id
+}
@@ -1,7 +1,10 @@
 
 pub mod ast;
+pub mod cst;
 pub mod token;
 pub mod file;
 pub mod attribute;
 pub mod layer;
-pub mod type_;
\ No newline at end of file
+pub mod type_;
+pub mod visit;
+pub mod fold;
\ No newline at end of file
@@ -19,6 +19,12 @@ impl std::fmt::Display for Token {
     }
 }
 
+/// The keywords and builtin type names, which cannot be used as identifiers.
+pub const KEYWORDS: &[&str] = &[
+    "let", "if", "else", "func", "return", "as", "import", "pub", "int", "float", "bool",
+    "string", "char", "void",
+];
+
 #[derive(Clone, PartialEq)]
 pub enum TokenKind {
     Identifier,
@@ -31,10 +37,14 @@ pub enum TokenKind {
     KeywordElse,
     KeywordFunc,
     KeywordReturn,
+    KeywordAs,
+    KeywordImport,
+    KeywordPub,
     TypeInt,
     TypeFloat,
     TypeBool,
     TypeString,
+    TypeChar,
     TypeVoid,
     ParenL,
     ParenR, 
@@ -71,10 +81,14 @@ impl std::fmt::Debug for TokenKind {
             TokenKind::KeywordElse => "<keyword else>",
             TokenKind::KeywordFunc => "<keyword func>",
             TokenKind::KeywordReturn => "<keyword return>",
+            TokenKind::KeywordAs => "<keyword as>",
+            TokenKind::KeywordImport => "<keyword import>",
+            TokenKind::KeywordPub => "<keyword pub>",
             TokenKind::TypeInt => "<type int>",
             TokenKind::TypeFloat => "<type float>",
             TokenKind::TypeBool => "<type bool>",
             TokenKind::TypeString => "<type string>",
+            TokenKind::TypeChar => "<type char>",
             TokenKind::TypeVoid => "<type void>",
             TokenKind::Symbol => "<symbol>",
             TokenKind::String => "<string>",
@@ -110,10 +124,14 @@ impl std::fmt::Display for TokenKind {
             TokenKind::KeywordElse => "else",
             TokenKind::KeywordFunc => "func",
             TokenKind::KeywordReturn => "return",
+            TokenKind::KeywordAs => "as",
+            TokenKind::KeywordImport => "import",
+            TokenKind::KeywordPub => "pub",
             TokenKind::TypeInt => "int",
             TokenKind::TypeFloat => "float",
             TokenKind::TypeBool => "bool",
             TokenKind::TypeString => "string",
+            TokenKind::TypeChar => "char",
             TokenKind::TypeVoid => "void",
             TokenKind::Comma => ",",
             TokenKind::Eq => "=",
@@ -1,10 +1,12 @@
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     Int,
     Bool,
     Void,
     Float,
     String,
+    Char,
     Function(Vec<Type>, Box<Type>), // Function type with parameters and return type
 }
 
@@ -16,6 +18,7 @@ impl std::fmt::Display for Type {
             Type::Void => write!(f, "void"),
             Type::Float => write!(f, "float"),
             Type::String => write!(f, "string"),
+            Type::Char => write!(f, "char"),
             Type::Function(params, return_type) => {
                 write!(f, "fn(")?;
                 for (i, param) in params.iter().enumerate() {
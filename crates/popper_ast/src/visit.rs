@@ -0,0 +1,166 @@
+//! Read-only traversal of a `LangAst`.
+//!
+//! Every `visit_*` method of `Visitor` defaults to the `walk_*` function of the same
+//! name, which visits the children of the node. A pass overrides the methods of the
+//! nodes it cares about, calling the `walk_*` function to keep going down.
+
+use crate::ast::{ArgumentParamDef, Expr, Ident, LangAst, LangNodeId, LangNodeKind, Let};
+
+pub trait Visitor<'ast>: Sized {
+    fn visit_node(&mut self, ast: &'ast LangAst, id: LangNodeId) {
+        walk_node(self, ast, id)
+    }
+
+    fn visit_expr(&mut self, ast: &'ast LangAst, id: LangNodeId, expr: &'ast Expr) {
+        walk_expr(self, ast, id, expr)
+    }
+
+    fn visit_let(&mut self, ast: &'ast LangAst, id: LangNodeId, let_: &'ast Let) {
+        walk_let(self, ast, id, let_)
+    }
+
+    fn visit_block(&mut self, ast: &'ast LangAst, id: LangNodeId, items: &'ast [LangNodeId]) {
+        walk_block(self, ast, id, items)
+    }
+
+    fn visit_if(
+        &mut self,
+        ast: &'ast LangAst,
+        id: LangNodeId,
+        condition: LangNodeId,
+        then_branch: LangNodeId,
+        else_branch: Option<LangNodeId>,
+    ) {
+        walk_if(self, ast, id, condition, then_branch, else_branch)
+    }
+
+    fn visit_function_call(&mut self, ast: &'ast LangAst, id: LangNodeId, function: LangNodeId, args: &'ast [LangNodeId]) {
+        walk_function_call(self, ast, id, function, args)
+    }
+
+    fn visit_return(&mut self, ast: &'ast LangAst, id: LangNodeId, value: LangNodeId) {
+        walk_return(self, ast, id, value)
+    }
+
+    /// Visits a function definition. The fields not needed to go down, such as its name
+    /// or attributes, are in `ast.get(id)`.
+    fn visit_function_def(
+        &mut self,
+        ast: &'ast LangAst,
+        id: LangNodeId,
+        params: &'ast [ArgumentParamDef],
+        body: Option<LangNodeId>,
+    ) {
+        walk_function_def(self, ast, id, params, body)
+    }
+
+    /// Visits a parameter of a function definition, which is not a node of its own.
+    fn visit_param(&mut self, _ast: &'ast LangAst, _param: &'ast ArgumentParamDef) {}
+
+    fn visit_import(&mut self, ast: &'ast LangAst, id: LangNodeId, path: &'ast [Ident]) {
+        walk_import(self, ast, id, path)
+    }
+}
+
+/// Calls the `visit_*` method of the kind of `id`.
+pub fn walk_node<'ast, V: Visitor<'ast>>(visitor: &mut V, ast: &'ast LangAst, id: LangNodeId) {
+    match &ast.get(id).kind {
+        LangNodeKind::Expr(expr) => visitor.visit_expr(ast, id, expr),
+        LangNodeKind::Let(let_) => visitor.visit_let(ast, id, let_),
+        LangNodeKind::Block(items) => visitor.visit_block(ast, id, items),
+        LangNodeKind::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => visitor.visit_if(ast, id, *condition, *then_branch, *else_branch),
+        LangNodeKind::FunctionCall { function, args } => visitor.visit_function_call(ast, id, *function, args),
+        LangNodeKind::Return(value) => visitor.visit_return(ast, id, *value),
+        LangNodeKind::FunctionDef { params, body, .. } => visitor.visit_function_def(ast, id, params, *body),
+        LangNodeKind::Import(path) => visitor.visit_import(ast, id, path),
+    }
+}
+
+pub fn walk_expr<'ast, V: Visitor<'ast>>(visitor: &mut V, ast: &'ast LangAst, _id: LangNodeId, expr: &'ast Expr) {
+    match expr {
+        Expr::Ident(_) | Expr::Int(_) | Expr::String(_) | Expr::Char(_) => {}
+        Expr::UnaryOp(_, value) | Expr::Cast(value, _) => visitor.visit_node(ast, *value),
+        Expr::Add(lhs, rhs) | Expr::Sub(lhs, rhs) | Expr::Mul(lhs, rhs) | Expr::Div(lhs, rhs) => {
+            visitor.visit_node(ast, *lhs);
+            visitor.visit_node(ast, *rhs);
+        }
+    }
+}
+
+pub fn walk_let<'ast, V: Visitor<'ast>>(visitor: &mut V, ast: &'ast LangAst, _id: LangNodeId, let_: &'ast Let) {
+    visitor.visit_node(ast, let_.value);
+}
+
+pub fn walk_block<'ast, V: Visitor<'ast>>(visitor: &mut V, ast: &'ast LangAst, _id: LangNodeId, items: &'ast [LangNodeId]) {
+    for &item in items {
+        visitor.visit_node(ast, item);
+    }
+}
+
+pub fn walk_if<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    ast: &'ast LangAst,
+    _id: LangNodeId,
+    condition: LangNodeId,
+    then_branch: LangNodeId,
+    else_branch: Option<LangNodeId>,
+) {
+    visitor.visit_node(ast, condition);
+    visitor.visit_node(ast, then_branch);
+    if let Some(else_branch) = else_branch {
+        visitor.visit_node(ast, else_branch);
+    }
+}
+
+pub fn walk_function_call<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    ast: &'ast LangAst,
+    _id: LangNodeId,
+    function: LangNodeId,
+    args: &'ast [LangNodeId],
+) {
+    visitor.visit_node(ast, function);
+    for &arg in args {
+        visitor.visit_node(ast, arg);
+    }
+}
+
+pub fn walk_return<'ast, V: Visitor<'ast>>(visitor: &mut V, ast: &'ast LangAst, _id: LangNodeId, value: LangNodeId) {
+    visitor.visit_node(ast, value);
+}
+
+pub fn walk_function_def<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    ast: &'ast LangAst,
+    _id: LangNodeId,
+    params: &'ast [ArgumentParamDef],
+    body: Option<LangNodeId>,
+) {
+    for param in params {
+        visitor.visit_param(ast, param);
+    }
+    if let Some(body) = body {
+        visitor.visit_node(ast, body);
+    }
+}
+
+pub fn walk_import<'ast, V: Visitor<'ast>>(_visitor: &mut V, _ast: &'ast LangAst, _id: LangNodeId, _path: &'ast [Ident]) {}
+
+/// The direct children of `id`, in source order.
+pub fn children(ast: &LangAst, id: LangNodeId) -> Vec<LangNodeId> {
+    struct Children(Vec<LangNodeId>);
+
+    impl Visitor<'_> for Children {
+        fn visit_node(&mut self, _ast: &LangAst, id: LangNodeId) {
+            self.0.push(id);
+        }
+    }
+
+    let mut children = Children(Vec::new());
+    walk_node(&mut children, ast, id);
+    children.0
+}
@@ -0,0 +1,36 @@
+use crate::PopperCodegenLLVM;
+use inkwell::builder::{Builder, BuilderError};
+use inkwell::values::BasicValueEnum;
+use popper_semantic_analyzer::cast::CastKind;
+use popper_semantic_analyzer::hir::{Hir, HirNodeId};
+
+impl PopperCodegenLLVM {
+    /// Lowers the `as` expression `node` of `hir`, `value` being its operand, with the
+    /// kind of cast recorded by the type checker.
+    pub fn build_cast<'ctx>(
+        &'ctx self,
+        builder: &Builder<'ctx>,
+        hir: &Hir,
+        node: HirNodeId,
+        value: BasicValueEnum<'ctx>,
+    ) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        let kind = hir
+            .get_cast_kind(node)
+            .expect("the type checker records the kind of every cast (it is a bug in the semantic analyzer)");
+        match kind {
+            CastKind::NoOp => Ok(value),
+            CastKind::IntToFloat => builder
+                .build_signed_int_to_float(value.into_int_value(), self.ctx.f64_type(), "cast")
+                .map(Into::into),
+            CastKind::FloatToInt => builder
+                .build_float_to_signed_int(value.into_float_value(), self.ctx.i64_type(), "cast")
+                .map(Into::into),
+            CastKind::BoolToInt | CastKind::CharToInt => builder
+                .build_int_z_extend(value.into_int_value(), self.ctx.i64_type(), "cast")
+                .map(Into::into),
+            CastKind::IntToChar => builder
+                .build_int_truncate(value.into_int_value(), self.ctx.i32_type(), "cast")
+                .map(Into::into),
+        }
+    }
+}
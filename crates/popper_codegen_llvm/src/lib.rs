@@ -1,3 +1,5 @@
+mod cast;
+
 use inkwell::context::Context;
 use popper_ast::layer::{Ast, Layer};
 use popper_semantic_analyzer::hir::{Hir, HirNodeId};
@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+/// Lines of unchanged text shown around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+impl Line<'_> {
+    fn in_old(self) -> bool {
+        !matches!(self, Line::Insert(_))
+    }
+
+    fn in_new(self) -> bool {
+        !matches!(self, Line::Delete(_))
+    }
+}
+
+/// The lines of `old` and `new` along a longest common subsequence. The lines they
+/// start and end with are matched first, so that a small change in a large file costs
+/// little.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(old, new)| old == new).count();
+    let (old_rest, new_rest) = (&old[prefix..], &new[prefix..]);
+    let suffix = old_rest.iter().rev().zip(new_rest.iter().rev()).take_while(|(old, new)| old == new).count();
+
+    let mut lines: Vec<Line> = old[..prefix].iter().map(|line| Line::Equal(line)).collect();
+    diff_between(&old_rest[..old_rest.len() - suffix], &new_rest[..new_rest.len() - suffix], &mut lines);
+    lines.extend(old[old.len() - suffix..].iter().map(|line| Line::Equal(line)));
+    lines
+}
+
+/// Hirschberg's algorithm, in space linear in the number of lines: finds where a longest
+/// common subsequence of `old` and `new` crosses the middle of `old`, then diffs both
+/// sides of it.
+fn diff_between<'a>(old: &[&'a str], new: &[&'a str], lines: &mut Vec<Line<'a>>) {
+    match old {
+        [] => lines.extend(new.iter().map(|line| Line::Insert(line))),
+        [line] => match new.iter().position(|new_line| new_line == line) {
+            Some(j) => {
+                lines.extend(new[..j].iter().map(|line| Line::Insert(line)));
+                lines.push(Line::Equal(line));
+                lines.extend(new[j + 1..].iter().map(|line| Line::Insert(line)));
+            }
+            None => {
+                lines.push(Line::Delete(line));
+                lines.extend(new.iter().map(|line| Line::Insert(line)));
+            }
+        },
+        _ if new.is_empty() => lines.extend(old.iter().map(|line| Line::Delete(line))),
+        _ => {
+            let middle = old.len() / 2;
+            let before = common_lengths(old[..middle].iter(), new.iter());
+            let after = common_lengths(old[middle..].iter().rev(), new.iter().rev());
+            // The first split of `new` with the longest common subsequence.
+            let mut split = 0;
+            for j in 1..=new.len() {
+                if before[j] + after[new.len() - j] > before[split] + after[new.len() - split] {
+                    split = j;
+                }
+            }
+            diff_between(&old[..middle], &new[..split], lines);
+            diff_between(&old[middle..], &new[split..], lines);
+        }
+    }
+}
+
+/// The length of the longest common subsequence of `old` and every prefix of `new`,
+/// computed one line of `old` at a time.
+fn common_lengths<'a>(
+    old: impl Iterator<Item = &'a &'a str>,
+    new: impl Iterator<Item = &'a &'a str> + Clone,
+) -> Vec<usize> {
+    let mut lengths = vec![0; new.clone().count() + 1];
+    for old_line in old {
+        // The length for the previous prefix of `new`, before this line of `old`.
+        let mut diagonal = 0;
+        for (j, new_line) in new.clone().enumerate() {
+            let above = lengths[j + 1];
+            lengths[j + 1] = if old_line == new_line {
+                diagonal + 1
+            } else {
+                above.max(lengths[j])
+            };
+            diagonal = above;
+        }
+    }
+    lengths
+}
+
+/// A hunk range, `start,len` with `start` 1-based, or the line before the hunk when it
+/// is empty.
+fn range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{len}", start + 1)
+    }
+}
+
+/// A unified diff from `old` to `new`, both being the contents of `path`. Empty when
+/// they have the same lines.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old_lines, &new_lines);
+    let is_change = |line: &Line| !matches!(line, Line::Equal(_));
+
+    let mut diff = String::new();
+    let mut next = 0;
+    while let Some(first) = lines[next..].iter().position(is_change).map(|i| i + next) {
+        // Changes separated by less than twice the context go in the same hunk.
+        let mut last = first;
+        while let Some(change) = lines[last + 1..].iter().position(is_change) {
+            if change > 2 * CONTEXT {
+                break;
+            }
+            last += change + 1;
+        }
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + 1 + CONTEXT).min(lines.len());
+        let hunk = &lines[start..end];
+
+        if diff.is_empty() {
+            let _ = writeln!(diff, "--- {path}\n+++ {path}");
+        }
+        let old_start = lines[..start].iter().filter(|line| line.in_old()).count();
+        let new_start = lines[..start].iter().filter(|line| line.in_new()).count();
+        let old_len = hunk.iter().filter(|line| line.in_old()).count();
+        let new_len = hunk.iter().filter(|line| line.in_new()).count();
+        let _ = writeln!(diff, "@@ -{} +{} @@", range(old_start, old_len), range(new_start, new_len));
+        for line in hunk {
+            let _ = match line {
+                Line::Equal(text) => writeln!(diff, " {text}"),
+                Line::Delete(text) => writeln!(diff, "-{text}"),
+                Line::Insert(text) => writeln!(diff, "+{text}"),
+            };
+        }
+        next = end;
+    }
+    diff
+}
@@ -0,0 +1,44 @@
+use popper_ast::ast::Span;
+use popper_error_core::Diagnostics;
+use popper_error_macro::Diagnostics;
+
+#[derive(Diagnostics, Debug)]
+#[message("Module `{module}` not found")]
+#[code = 2001]
+#[label = "no file at `{path}`"]
+#[note = "Modules are resolved relative to the importing file, `import foo.bar;` loads `foo/bar.pop`"]
+pub struct ModuleNotFound {
+    pub module: String,
+    pub path: String,
+
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("Module `{module}` is imported in a cycle")]
+#[code = 2002]
+#[label = "this import leads back to the current module"]
+#[note = "Move the items both modules need to a separate module"]
+pub struct ImportCycle {
+    pub module: String,
+
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Debug, Diagnostics)]
+pub enum DriverError {
+    ModuleNotFound(ModuleNotFound),
+    ImportCycle(ImportCycle),
+}
+
+impl DriverError {
+    pub fn module_not_found(module: String, path: String, span: Span) -> Self {
+        DriverError::ModuleNotFound(ModuleNotFound { module, path, span })
+    }
+
+    pub fn import_cycle(module: String, span: Span) -> Self {
+        DriverError::ImportCycle(ImportCycle { module, span })
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{check, load_modules_with};
+use popper_ast::file::FileId;
+use popper_context::Context;
+use popper_error_core::lint::LintLevels;
+use popper_error_core::{ErrorTable, Suggestion};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// How many times `fix_program` applies suggestions and checks the program again at
+/// most, in case fixes keep leading to new suggestions.
+pub const MAX_FIX_PASSES: usize = 16;
+
+/// A source file rewritten by applying the suggestions of its diagnostics.
+#[derive(Debug, Clone)]
+pub struct FixedFile {
+    pub file: FileId,
+    pub path: String,
+    pub source: String,
+    pub fixed: String,
+    /// How many suggestions were applied.
+    pub applied: usize,
+}
+
+/// The result of `fix_program`: the fixed files, and the program they make up along
+/// with what is still reported about it.
+pub struct FixedProgram {
+    pub context: Context,
+    pub errors: ErrorTable,
+    pub files: Vec<FixedFile>,
+}
+
+/// Fixes the program whose entry point is `path`. The parser and the analyzer stop at
+/// the first error, so this checks the program, applies the suggestions and checks the
+/// fixed sources again until no suggestion changes them, at most `MAX_FIX_PASSES`
+/// times. Nothing is written, the fixed files hold their original and final source.
+///
+/// Returns `None` if `path` cannot be read.
+pub fn fix_program(path: &str, lints: &LintLevels) -> Option<FixedProgram> {
+    let mut sources: HashMap<PathBuf, String> = HashMap::new();
+    let mut files: Vec<FixedFile> = Vec::new();
+    let mut passes = 0;
+    loop {
+        let mut context = Context::new();
+        let mut errors = ErrorTable::with_lints(lints.clone());
+        let modules = load_modules_with(&mut context, path, &sources, &mut errors)?;
+        if !errors.has_errors() {
+            check(&context, &modules, &mut errors);
+        }
+
+        let changed: Vec<FixedFile> = fix_files(&context, &errors)
+            .into_iter()
+            .filter(|file| file.fixed != file.source)
+            .collect();
+        if changed.is_empty() || passes == MAX_FIX_PASSES {
+            // The file ids of the earlier passes refer to their own contexts.
+            for file in &mut files {
+                let path = Path::new(&file.path);
+                if let Some(fixed) = context.file_table().iter().find(|f| Path::new(f.info().path()) == path) {
+                    file.file = fixed.id();
+                }
+            }
+            return Some(FixedProgram { context, errors, files });
+        }
+        passes += 1;
+
+        for change in changed {
+            sources.insert(PathBuf::from(&change.path), change.fixed.clone());
+            match files.iter_mut().find(|file| file.path == change.path) {
+                Some(file) => {
+                    file.fixed = change.fixed;
+                    file.applied += change.applied;
+                }
+                None => files.push(change),
+            }
+        }
+    }
+}
+
+/// Applies the suggestions of `errors` to the files of `context` they were reported
+/// in. Files without suggestions are left out.
+pub fn fix_files(context: &Context, errors: &ErrorTable) -> Vec<FixedFile> {
+    let mut by_file: Vec<(FileId, Vec<Suggestion>)> = Vec::new();
+    for (file, suggestion) in errors.suggestions() {
+        match by_file.iter_mut().find(|(id, _)| *id == file) {
+            Some((_, suggestions)) => suggestions.push(suggestion),
+            None => by_file.push((file, vec![suggestion])),
+        }
+    }
+
+    by_file
+        .into_iter()
+        .filter_map(|(file, suggestions)| {
+            let info = context.get_file(file)?.info();
+            let (fixed, applied) = apply_suggestions(info.source(), suggestions);
+            Some(FixedFile {
+                file,
+                path: info.path().to_string(),
+                source: info.source().to_string(),
+                fixed,
+                applied,
+            })
+        })
+        .collect()
+}
+
+/// Applies `suggestions` to `source` in source order, returning the new source and the
+/// number of suggestions applied. A suggestion overlapping one already applied is
+/// skipped, as is an insertion at the same offset as another one, since the order of
+/// the inserted texts would be arbitrary. Duplicate suggestions are applied once.
+pub fn apply_suggestions(source: &str, mut suggestions: Vec<Suggestion>) -> (String, usize) {
+    suggestions.sort_by_key(|suggestion| (suggestion.span.lo, suggestion.span.hi));
+    suggestions.dedup();
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut copied = 0;
+    let mut last_insertion = None;
+    let mut applied = 0;
+    for suggestion in suggestions {
+        let span = suggestion.span;
+        let is_insertion = span.lo == span.hi;
+        if span.lo < copied || span.hi > source.len() || (is_insertion && last_insertion == Some(span.lo)) {
+            continue;
+        }
+        fixed.push_str(&source[copied..span.lo]);
+        fixed.push_str(&suggestion.replacement);
+        copied = span.hi;
+        if is_insertion {
+            last_insertion = Some(span.lo);
+        }
+        applied += 1;
+    }
+    fixed.push_str(&source[copied..]);
+    (fixed, applied)
+}
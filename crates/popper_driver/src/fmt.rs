@@ -0,0 +1,342 @@
+use crate::report;
+use popper_ast::ast::{
+    ArgumentParamDef, Expr, Ident, LangAst, LangNodeId, LangNodeKind, Let, Span, SymbolId, UnaryOpKind, Visibility,
+};
+use popper_ast::attribute::Attribute;
+use popper_ast::file::{FileId, SourceFileInfo};
+use popper_ast::token::Token;
+use popper_ast::type_::Type;
+use popper_ast::visit::Visitor;
+use popper_context::Context;
+use popper_error_core::ErrorTable;
+use popper_parser::Parser;
+
+const INDENT: &str = "    ";
+
+/// A source file along with its text in the canonical style.
+#[derive(Debug, Clone)]
+pub struct FormattedFile {
+    pub file: FileId,
+    pub path: String,
+    pub source: String,
+    pub formatted: String,
+}
+
+/// Reads the file at `path` into `context` and formats it, without loading the modules
+/// it imports. Returns `None` if the file cannot be read or parsed, the parse error
+/// being added to `errors`.
+pub fn format_file(context: &mut Context, path: &str, errors: &mut ErrorTable) -> Option<FormattedFile> {
+    let info = SourceFileInfo::from_file(path)?;
+    let file = context.file_table_mut().insert(info.clone(), None);
+    let formatted = match format_source(info.source()) {
+        Ok(formatted) => formatted,
+        Err(err) => {
+            report(errors, file, err);
+            return None;
+        }
+    };
+    Some(FormattedFile {
+        file,
+        path: path.to_string(),
+        source: info.source().to_string(),
+        formatted,
+    })
+}
+
+/// Parses `source` and prints it back in the canonical style.
+pub fn format_source(source: &str) -> popper_parser::error::Result<String> {
+    let mut parser = Parser::new(source);
+    let ast = parser.parse()?;
+    Ok(format(source, &ast, parser.comments()))
+}
+
+/// Prints `ast`, parsed from `source`, in the canonical style: one statement per line,
+/// blocks indented by four spaces, spaces around binary operators and after commas, and
+/// at most one blank line where `source` has some. Literals are kept as written.
+///
+/// The `comments` of `source` are kept on their own line before the statement following
+/// them, or at the end of the line of the statement they are in.
+pub fn format(source: &str, ast: &LangAst, comments: &[Token]) -> String {
+    let mut formatter = Formatter {
+        source,
+        ast,
+        comments,
+        next_comment: 0,
+        indent: 0,
+        out: String::new(),
+    };
+    let mut previous = None;
+    if let Some(module_attrs) = &ast.module_attrs {
+        previous = formatter.comments_before(module_attrs.span.lo, None);
+        formatter.blank_line(previous, module_attrs.span.lo);
+        let attrs: Vec<String> = module_attrs.attrs.iter().map(attribute).collect();
+        formatter.out.push_str(&format!("module({});", attrs.join(", ")));
+        let end = module_attrs.span.hi;
+        previous = Some(formatter.trailing_comments(end).map_or(end, |last| last.max(end)));
+        formatter.out.push('\n');
+    }
+    if let LangNodeKind::Block(items) = &ast.get(ast.root).kind {
+        formatter.items(items, source.len(), previous);
+    }
+    formatter.out
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    ast: &'a LangAst,
+    comments: &'a [Token],
+    /// The first comment not written yet.
+    next_comment: usize,
+    indent: usize,
+    out: String,
+}
+
+impl<'a> Formatter<'a> {
+    /// Writes `items` one per line, then the comments before `end`. `previous` is the
+    /// end of what was written before, if it is to be kept apart by a blank line.
+    fn items(&mut self, items: &[LangNodeId], end: usize, mut previous: Option<usize>) {
+        for &item in items {
+            let start = self.start(item);
+            previous = self.comments_before(start, previous);
+            self.blank_line(previous, start);
+            self.out.push_str(&INDENT.repeat(self.indent));
+            self.visit_node(self.ast, item);
+            if let LangNodeKind::Expr(_) | LangNodeKind::FunctionCall { .. } = self.ast.get(item).kind {
+                self.out.push(';');
+            }
+            let end = self.end(self.ast.get(item).span);
+            // A comment inside the statement is moved after it, blank lines are counted
+            // from the end of the statement.
+            previous = Some(self.trailing_comments(end).map_or(end, |last| last.max(end)));
+            self.out.push('\n');
+        }
+        self.comments_before(end, previous);
+    }
+
+    /// Writes the comments starting before `lo` on their own line, returning the end of
+    /// the last one, or `previous` if there are none.
+    fn comments_before(&mut self, lo: usize, mut previous: Option<usize>) -> Option<usize> {
+        let comments = self.comments;
+        while let Some(comment) = comments.get(self.next_comment).filter(|comment| comment.span.lo < lo) {
+            self.blank_line(previous, comment.span.lo);
+            self.out.push_str(&INDENT.repeat(self.indent));
+            self.out.push_str(&comment.value);
+            self.out.push('\n');
+            previous = Some(comment.span.hi);
+            self.next_comment += 1;
+        }
+        previous
+    }
+
+    /// Writes the comments inside the statement ending at `end` and the ones following it
+    /// on the same line, returning the end of the last one.
+    fn trailing_comments(&mut self, end: usize) -> Option<usize> {
+        let comments = self.comments;
+        let mut last = None;
+        while let Some(comment) = comments.get(self.next_comment) {
+            let on_line = comment.span.lo < end || !self.source[end..comment.span.lo].contains('\n');
+            if !on_line {
+                break;
+            }
+            self.out.push(' ');
+            self.out.push_str(&comment.value);
+            last = Some(comment.span.hi);
+            self.next_comment += 1;
+        }
+        last
+    }
+
+    /// Keeps one blank line between `previous` and `lo` if the source has any.
+    fn blank_line(&mut self, previous: Option<usize>, lo: usize) {
+        let Some(between) = previous.and_then(|previous| self.source.get(previous..lo)) else {
+            return;
+        };
+        if between.matches('\n').count() > 1 {
+            self.out.push('\n');
+        }
+    }
+
+    /// The offset of the first token of `id`. The span of a binary expression starts at
+    /// its operator.
+    fn start(&self, id: LangNodeId) -> usize {
+        let node = self.ast.get(id);
+        match &node.kind {
+            LangNodeKind::Expr(
+                Expr::Add(lhs, _) | Expr::Sub(lhs, _) | Expr::Mul(lhs, _) | Expr::Div(lhs, _) | Expr::Cast(lhs, _),
+            ) => self.start(*lhs),
+            LangNodeKind::FunctionCall { function, .. } => self.start(*function),
+            _ => node.span.lo,
+        }
+    }
+
+    /// The end of `span` without the blanks it may cover after its last token.
+    fn end(&self, span: Span) -> usize {
+        span.lo + self.source[span.lo..span.hi].trim_end().len()
+    }
+
+    fn name(&self, id: SymbolId) -> &'a str {
+        &self.ast.get_symbol(id).name
+    }
+
+    fn binary(&mut self, ast: &'a LangAst, lhs: LangNodeId, op: &str, rhs: LangNodeId) {
+        self.visit_node(ast, lhs);
+        self.out.push_str(op);
+        self.visit_node(ast, rhs);
+    }
+}
+
+/// Writes the node it visits, a statement without its indentation or an expression.
+impl<'a> Visitor<'a> for Formatter<'a> {
+    fn visit_expr(&mut self, ast: &'a LangAst, id: LangNodeId, expr: &'a Expr) {
+        match expr {
+            Expr::Ident(name) => self.out.push_str(self.name(name.0)),
+            // Kept as written, e.g. `0xFF` or `r"\d"`.
+            Expr::Int(_) | Expr::String(_) | Expr::Char(_) => {
+                let span = ast.get(id).span;
+                self.out.push_str(&self.source[span.lo..span.hi]);
+            }
+            Expr::UnaryOp(op, value) => {
+                self.out.push_str(match op {
+                    UnaryOpKind::Negate => "!",
+                    UnaryOpKind::ArithmeticNegate => "-",
+                    UnaryOpKind::ArithmeticPlus => "+",
+                });
+                self.visit_node(ast, *value);
+            }
+            Expr::Add(lhs, rhs) => self.binary(ast, *lhs, " + ", *rhs),
+            Expr::Sub(lhs, rhs) => self.binary(ast, *lhs, " - ", *rhs),
+            Expr::Mul(lhs, rhs) => self.binary(ast, *lhs, " * ", *rhs),
+            Expr::Div(lhs, rhs) => self.binary(ast, *lhs, " / ", *rhs),
+            Expr::Cast(value, ty) => {
+                self.visit_node(ast, *value);
+                self.out.push_str(&format!(" as {ty}"));
+            }
+        }
+    }
+
+    fn visit_function_call(&mut self, ast: &'a LangAst, _id: LangNodeId, function: LangNodeId, args: &'a [LangNodeId]) {
+        self.visit_node(ast, function);
+        self.out.push('(');
+        for (i, &arg) in args.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            self.visit_node(ast, arg);
+        }
+        self.out.push(')');
+    }
+
+    fn visit_let(&mut self, ast: &'a LangAst, _id: LangNodeId, let_: &'a Let) {
+        self.out.push_str(visibility(let_.visibility));
+        self.out.push_str(&format!("let {} = ", self.name(let_.name.0)));
+        self.visit_node(ast, let_.value);
+        self.out.push(';');
+    }
+
+    fn visit_function_def(
+        &mut self,
+        ast: &'a LangAst,
+        id: LangNodeId,
+        params: &'a [ArgumentParamDef],
+        body: Option<LangNodeId>,
+    ) {
+        let LangNodeKind::FunctionDef {
+            name,
+            visibility: vis,
+            attrs,
+            ret,
+            is_expr,
+            ..
+        } = &ast.get(id).kind
+        else {
+            return;
+        };
+        self.out.push_str(visibility(*vis));
+        self.out.push_str("func");
+        if !attrs.is_empty() {
+            let attrs: Vec<String> = attrs.iter().map(attribute).collect();
+            self.out.push_str(&format!("({})", attrs.join(", ")));
+        }
+        let params: Vec<String> = params
+            .iter()
+            .map(|param| format!("{}: {}", self.name(param.name.0), param.ty))
+            .collect();
+        self.out.push_str(&format!(" {}({})", self.name(name.0), params.join(", ")));
+        if *ret != Type::Void {
+            self.out.push_str(&format!(" -> {ret}"));
+        }
+        match body {
+            Some(body) if *is_expr => {
+                self.out.push_str(" = ");
+                self.visit_node(ast, body);
+            }
+            Some(body) => {
+                self.out.push(' ');
+                self.visit_node(ast, body);
+            }
+            None => self.out.push(';'),
+        }
+    }
+
+    fn visit_if(
+        &mut self,
+        ast: &'a LangAst,
+        _id: LangNodeId,
+        condition: LangNodeId,
+        then_branch: LangNodeId,
+        else_branch: Option<LangNodeId>,
+    ) {
+        self.out.push_str("if ");
+        self.visit_node(ast, condition);
+        self.out.push(' ');
+        self.visit_node(ast, then_branch);
+        if let Some(else_branch) = else_branch {
+            self.out.push_str(" else ");
+            self.visit_node(ast, else_branch);
+        }
+    }
+
+    fn visit_return(&mut self, ast: &'a LangAst, _id: LangNodeId, value: LangNodeId) {
+        self.out.push_str("return ");
+        self.visit_node(ast, value);
+        self.out.push(';');
+    }
+
+    fn visit_import(&mut self, _ast: &'a LangAst, _id: LangNodeId, path: &'a [Ident]) {
+        let path: Vec<&str> = path.iter().map(|segment| self.name(segment.0)).collect();
+        self.out.push_str(&format!("import {};", path.join(".")));
+    }
+
+    fn visit_block(&mut self, ast: &'a LangAst, id: LangNodeId, items: &'a [LangNodeId]) {
+        let span = ast.get(id).span;
+        let has_comments = self
+            .comments
+            .get(self.next_comment)
+            .is_some_and(|comment| comment.span.lo < span.hi);
+        if items.is_empty() && !has_comments {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push_str("{\n");
+        self.indent += 1;
+        self.items(items, span.hi, None);
+        self.indent -= 1;
+        self.out.push_str(&INDENT.repeat(self.indent));
+        self.out.push('}');
+    }
+}
+
+fn visibility(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "pub ",
+        Visibility::Private => "",
+    }
+}
+
+fn attribute(attribute: &Attribute) -> String {
+    match attribute {
+        Attribute::StdCallC => "C".to_string(),
+        Attribute::Lint(level, lints) => format!("{}({})", level.name(), lints.join(", ")),
+    }
+}
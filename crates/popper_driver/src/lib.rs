@@ -0,0 +1,101 @@
+pub mod diff;
+pub mod error;
+pub mod fix;
+pub mod fmt;
+pub mod module;
+
+pub use module::{import_path, load_modules, load_modules_with, Module};
+
+use popper_ast::ast::LineInfo;
+use popper_ast::file::FileId;
+use popper_context::Context;
+use popper_error_core::{Diagnostics, Error, ErrorInfo, ErrorTable};
+use popper_semantic_analyzer::module::Export;
+use popper_semantic_analyzer::symbol_resolver::SymbolResolver;
+use popper_semantic_analyzer::type_checker::TypeChecker;
+use popper_semantic_analyzer::SemanticAnalyzer;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Runs the semantic analyzer over `modules` in order, making the exports of every
+/// imported module visible to the module importing it. The exports of the other
+/// modules are only used to hint at missing imports. Warnings are reported along with
+/// the errors; stops at the first module with an error.
+///
+/// Returns the analyzer of every module analyzed, the last one being the module with
+/// the error if any.
+pub fn check(context: &Context, modules: &[Module], errors: &mut ErrorTable) -> Vec<(FileId, SemanticAnalyzer)> {
+    check_with_scope_query(context, modules, errors, None)
+}
+
+/// Like `check`, but the analyzer of the module `query.0` also records the symbols in
+/// scope at the byte offset `query.1`, see `SemanticAnalyzer::scope_at_query`.
+pub fn check_with_scope_query(
+    context: &Context,
+    modules: &[Module],
+    errors: &mut ErrorTable,
+    query: Option<(FileId, usize)>,
+) -> Vec<(FileId, SemanticAnalyzer)> {
+    let mut analyzed = Vec::new();
+    let mut exports: HashMap<FileId, Vec<Export>> = HashMap::new();
+
+    for module in modules {
+        let Some(file) = context.get_file(module.file) else {
+            continue;
+        };
+        let Some(ast) = file.ast() else {
+            continue;
+        };
+
+        let mut analyzer = SemanticAnalyzer::new(ast.clone());
+        analyzer.add_type_checker_layer(TypeChecker::new());
+        let resolver = match query {
+            Some((file, offset)) if file == module.file => SymbolResolver::with_scope_query(offset),
+            _ => SymbolResolver::new(),
+        };
+        analyzer.add_symbol_resolver_layer(resolver);
+        for import in &module.imports {
+            for export in exports.get(import).into_iter().flatten() {
+                analyzer.import_symbol(export, *import);
+            }
+        }
+        for other in modules {
+            if other.file == module.file || module.imports.contains(&other.file) {
+                continue;
+            }
+            let Some(other_exports) = exports.get(&other.file) else {
+                continue;
+            };
+            let other_path = context.get_file(other.file).map(|other| other.info().path());
+            let importer = Path::new(file.info().path());
+            if let Some(path) = other_path.and_then(|path| import_path(importer, Path::new(path))) {
+                analyzer.add_unimported_module(path, other_exports);
+            }
+        }
+
+        let result = analyzer.analyze(ast.root);
+        for (warning, level) in analyzer.take_warnings() {
+            let line_info = LineInfo::from_span(warning.span(), 1);
+            let error = Error::new(ErrorInfo::new(line_info, module.file), warning);
+            errors.add_error(error.with_lint_level(level));
+        }
+        let failed = match result {
+            Ok(_) => false,
+            Err(err) => {
+                report(errors, module.file, err);
+                true
+            }
+        };
+        exports.insert(module.file, analyzer.exports());
+        analyzed.push((module.file, analyzer));
+        if failed {
+            break;
+        }
+    }
+    analyzed
+}
+
+pub(crate) fn report<T: Diagnostics + 'static>(errors: &mut ErrorTable, file: FileId, diagnostics: T) {
+    let line_info = LineInfo::from_span(diagnostics.span(), 1);
+    errors.add_error(Error::new(ErrorInfo::new(line_info, file), diagnostics));
+}
@@ -0,0 +1,143 @@
+use crate::error::DriverError;
+use crate::report;
+use popper_ast::ast::{LangAst, LangNodeKind, Span};
+use popper_ast::file::{FileId, SourceFileInfo};
+use popper_context::Context;
+use popper_error_core::ErrorTable;
+use popper_parser::Parser;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A parsed source file along with the files it imports.
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub file: FileId,
+    pub imports: Vec<FileId>,
+}
+
+/// Parses the file at `path` and every module it imports, directly or not, into the
+/// file table of `context`. Modules are returned in dependency order: a module always
+/// comes after the modules it imports.
+///
+/// Returns `None` if `path` itself cannot be read, other failures are added to `errors`.
+pub fn load_modules(context: &mut Context, path: &str, errors: &mut ErrorTable) -> Option<Vec<Module>> {
+    load_modules_with(context, path, &HashMap::new(), errors)
+}
+
+/// Like `load_modules`, but the files in `sources` are read from there rather than from
+/// the disk, e.g. for the unsaved buffers of an editor. They are looked up by the path
+/// they are imported with, relative to the path of the importing file.
+pub fn load_modules_with(
+    context: &mut Context,
+    path: &str,
+    sources: &HashMap<PathBuf, String>,
+    errors: &mut ErrorTable,
+) -> Option<Vec<Module>> {
+    let mut loader = ModuleLoader {
+        context,
+        errors,
+        sources,
+        loaded: HashMap::new(),
+        in_progress: Vec::new(),
+        modules: Vec::new(),
+    };
+    loader.load(Path::new(path))?;
+    Some(loader.modules)
+}
+
+struct ModuleLoader<'a> {
+    context: &'a mut Context,
+    errors: &'a mut ErrorTable,
+    sources: &'a HashMap<PathBuf, String>,
+    loaded: HashMap<PathBuf, FileId>,
+    in_progress: Vec<FileId>,
+    modules: Vec<Module>,
+}
+
+impl ModuleLoader<'_> {
+    fn load(&mut self, path: &Path) -> Option<FileId> {
+        let source = self.sources.get(path);
+        let canonical = match std::fs::canonicalize(path) {
+            Ok(canonical) => canonical,
+            Err(_) if source.is_some() => path.to_path_buf(),
+            Err(_) => return None,
+        };
+        if let Some(&id) = self.loaded.get(&canonical) {
+            return Some(id);
+        }
+
+        let info = match source {
+            Some(source) => SourceFileInfo::from_source(path.to_str()?, source.clone()),
+            None => SourceFileInfo::from_file(path.to_str()?)?,
+        };
+        let id = self.context.file_table_mut().insert(info.clone(), None);
+        self.loaded.insert(canonical, id);
+
+        let ast = match Parser::from_source_file(info).parse() {
+            Ok(ast) => ast,
+            Err(err) => {
+                report(self.errors, id, err);
+                return Some(id);
+            }
+        };
+
+        self.in_progress.push(id);
+        let mut imports = Vec::new();
+        for (segments, span) in imports_of(&ast) {
+            let module = segments.join(".");
+            let import_path = resolve_import(path, &segments);
+            match self.load(&import_path) {
+                Some(import) if self.in_progress.contains(&import) => {
+                    report(self.errors, id, DriverError::import_cycle(module, span));
+                }
+                Some(import) => imports.push(import),
+                None => {
+                    let path = import_path.display().to_string();
+                    report(self.errors, id, DriverError::module_not_found(module, path, span));
+                }
+            }
+        }
+        self.in_progress.pop();
+
+        self.context.file_table_mut().get_mut(id)?.set_ast(ast);
+        self.modules.push(Module { file: id, imports });
+        Some(id)
+    }
+}
+
+/// `import foo.bar;` in `src/main.pop` resolves to `src/foo/bar.pop`.
+fn resolve_import(importer: &Path, segments: &[String]) -> PathBuf {
+    let mut path = importer.parent().unwrap_or(Path::new("")).to_path_buf();
+    path.extend(segments);
+    path.set_extension("pop");
+    path
+}
+
+/// The inverse of `resolve_import`: the path to write in `importer` to import the
+/// module at `module`, `None` if it is not below the directory of `importer`.
+pub fn import_path(importer: &Path, module: &Path) -> Option<String> {
+    let directory = importer.parent().unwrap_or(Path::new(""));
+    let relative = module.strip_prefix(directory).ok()?.with_extension("");
+    let segments: Option<Vec<&str>> = relative.iter().map(|segment| segment.to_str()).collect();
+    Some(segments?.join("."))
+}
+
+fn imports_of(ast: &LangAst) -> Vec<(Vec<String>, Span)> {
+    let LangNodeKind::Block(items) = &ast.get(ast.root).kind else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|&id| {
+            let node = ast.get(id);
+            match &node.kind {
+                LangNodeKind::Import(path) => {
+                    let segments = path.iter().map(|x| ast.get_symbol(x.0).name.clone()).collect();
+                    Some((segments, node.span))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,44 @@
+use popper_ast::ast::LangAst;
+use popper_error_core::Diagnostics;
+use popper_parser::Parser;
+use popper_semantic_analyzer::symbol_resolver::SymbolResolver;
+use popper_semantic_analyzer::type_checker::TypeChecker;
+use popper_semantic_analyzer::SemanticAnalyzer;
+use std::path::Path;
+
+/// The messages of the error and warnings of the analysis of `ast`, and the type found
+/// for every node.
+fn analyze(ast: &LangAst) -> (Option<String>, Vec<String>, Vec<Option<String>>) {
+    let mut analyzer = SemanticAnalyzer::new(ast.clone());
+    analyzer.add_type_checker_layer(TypeChecker::new());
+    analyzer.add_symbol_resolver_layer(SymbolResolver::new());
+    let error = analyzer.analyze(ast.root).err().map(|err| err.message());
+    let warnings = analyzer.take_warnings().into_iter().map(|(warning, _)| warning.message()).collect();
+    let types = (0..ast.nodes.len())
+        .map(|id| {
+            let id = popper_ast::ast::LangNodeId(id as u32);
+            analyzer.hir().get_type(id.into()).map(ToString::to_string)
+        })
+        .collect();
+    (error, warnings, types)
+}
+
+#[test]
+fn test_ast_json_round_trip() {
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples");
+    let mut sources = vec![
+        "func main() {\n    let x = 1;\n    let y = x + unknown;\n}\n".to_string(),
+        "func(C, allow(unused)) f(s: string) -> int;\nfunc main() = f(\"a\\n\") as int\n".to_string(),
+    ];
+    for name in ["arithmetic.pop", "branches.pop"] {
+        sources.push(std::fs::read_to_string(examples.join(name)).unwrap());
+    }
+
+    for source in sources {
+        let ast = Parser::new(&source).parse().unwrap();
+        let json = serde_json::to_string(&ast).unwrap();
+        let deserialized: LangAst = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+        assert_eq!(analyze(&deserialized), analyze(&ast), "{source}");
+    }
+}
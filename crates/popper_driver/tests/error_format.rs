@@ -0,0 +1,156 @@
+use popper_context::Context;
+use popper_driver::{check, load_modules_with};
+use popper_error_core::{registry, ErrorTable};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Loads and checks the program made of `files`, whose entry point is the first one.
+fn check_sources(files: &[(&str, &str)]) -> (Context, ErrorTable) {
+    let mut context = Context::new();
+    let mut errors = ErrorTable::new();
+    let sources: HashMap<_, _> = files
+        .iter()
+        .map(|(path, source)| (PathBuf::from(path), source.to_string()))
+        .collect();
+    let modules = load_modules_with(&mut context, files[0].0, &sources, &mut errors).unwrap();
+    check(&context, &modules, &mut errors);
+    (context, errors)
+}
+
+const PROGRAM: [(&str, &str); 2] = [
+    ("src/main.pop", "import foo;\n\nfunc main() {\n    secret();\n}\n"),
+    ("src/foo.pop", "func secret() {}\n\nfunc unused(n: int) {}\n"),
+];
+
+#[test]
+fn test_json() {
+    let (context, errors) = check_sources(&PROGRAM);
+    let json: Vec<String> = errors.iter().map(|error| error.to_json(&context).unwrap()).collect();
+    assert_eq!(json.len(), 4);
+    assert_eq!(
+        json[0],
+        concat!(
+            r#"{"code":"E1012","severity":"warning","lint":"unused_parameters","message":"Unused parameter `n`","#,
+            r#""label":"`n` is never used","note":null,"help":null,"file":"src/foo.pop","#,
+            r#""span":{"start":30,"end":36,"line_start":3,"column_start":13,"line_end":3,"column_end":19},"#,
+            r#""secondary_labels":[],"suggestions":[{"message":"if this is intentional, prefix it with an underscore","#,
+            r#""span":{"start":30,"end":31,"line_start":3,"column_start":13,"line_end":3,"column_end":14},"replacement":"_n"}]}"#,
+        )
+    );
+    assert_eq!(
+        json[3],
+        concat!(
+            r#"{"code":"E1007","severity":"error","lint":null,"message":"Symbol `secret` is private","#,
+            r#""label":"private symbol","note":null,"help":"Mark `secret` as `pub` in its module to use it here","#,
+            r#""file":"src/main.pop","span":{"start":31,"end":37,"line_start":4,"column_start":5,"line_end":4,"column_end":11},"#,
+            r#""secondary_labels":[{"message":"`secret` is defined here","file":"src/foo.pop","#,
+            r#""span":{"start":0,"end":16,"line_start":1,"column_start":1,"line_end":1,"column_end":17}}],"suggestions":[]}"#,
+        )
+    );
+}
+
+fn location(uri: &str, byte_offset: usize, byte_length: usize, start: (usize, usize), end: (usize, usize)) -> Value {
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": uri },
+            "region": {
+                "startLine": start.0,
+                "startColumn": start.1,
+                "endLine": end.0,
+                "endColumn": end.1,
+                "byteOffset": byte_offset,
+                "byteLength": byte_length,
+            },
+        },
+    })
+}
+
+#[test]
+fn test_sarif() {
+    let (context, errors) = check_sources(&PROGRAM);
+    let sarif = errors.to_sarif(&context).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+
+    // One rule per code, described by the explanation of the code.
+    let rule = |code: u32, lint: Option<&str>| {
+        let error_code = registry::lookup(code).unwrap();
+        let mut rule = json!({
+            "id": registry::code_name(code),
+            "shortDescription": { "text": error_code.summary() },
+            "help": { "text": error_code.explanation, "markdown": error_code.explanation },
+        });
+        if let Some(lint) = lint {
+            rule["name"] = json!(lint);
+        }
+        rule
+    };
+    let rules = &run["tool"]["driver"]["rules"];
+    assert_eq!(
+        *rules,
+        json!([
+            rule(1012, Some("unused_parameters")),
+            rule(1013, Some("unused_functions")),
+            rule(1007, None),
+        ])
+    );
+    assert_eq!(rules[1]["shortDescription"]["text"], "A private function is never called.");
+
+    let results = run["results"].as_array().unwrap();
+    let rules: Vec<_> = results.iter().map(|result| (&result["ruleId"], &result["ruleIndex"])).collect();
+    assert_eq!(
+        rules,
+        [
+            (&json!("E1012"), &json!(0)),
+            (&json!("E1013"), &json!(1)),
+            (&json!("E1013"), &json!(1)),
+            (&json!("E1007"), &json!(2)),
+        ]
+    );
+
+    assert_eq!(
+        results[0]["fixes"],
+        json!([{
+            "description": { "text": "if this is intentional, prefix it with an underscore" },
+            "artifactChanges": [{
+                "artifactLocation": { "uri": "src/foo.pop" },
+                "replacements": [{
+                    "deletedRegion": location("src/foo.pop", 30, 1, (3, 13), (3, 14))["physicalLocation"]["region"],
+                    "insertedContent": { "text": "_n" },
+                }],
+            }],
+        }])
+    );
+    assert_eq!(results[3]["level"], "error");
+    // The note of a diagnostic, which can name the symbol, stays with its result.
+    assert_eq!(
+        results[1]["message"]["text"],
+        "Unused function `secret`\nPrefix it with an underscore (`_secret`) or mark it `pub` to silence this warning"
+    );
+    assert_eq!(
+        results[3]["message"]["text"],
+        "Symbol `secret` is private\nMark `secret` as `pub` in its module to use it here"
+    );
+    assert_eq!(
+        results[3]["locations"],
+        json!([location("src/main.pop", 31, 6, (4, 5), (4, 11))])
+    );
+    let mut related = location("src/foo.pop", 0, 16, (1, 1), (1, 17));
+    related["id"] = json!(0);
+    related["message"] = json!({ "text": "`secret` is defined here" });
+    assert_eq!(results[3]["relatedLocations"], json!([related]));
+}
+
+#[test]
+fn test_sarif_uri() {
+    let uri = |path: &str| {
+        let (context, errors) = check_sources(&[(path, "func main() {\n    let x = 1;\n}\n")]);
+        let sarif = errors.to_sarif(&context).unwrap();
+        sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"].clone()
+    };
+    assert_eq!(uri("main.pop"), "main.pop");
+    assert_eq!(uri("my project/main.pop"), "my%20project/main.pop");
+    assert_eq!(uri("/home/me/my project/main.pop"), "file:///home/me/my%20project/main.pop");
+    assert_eq!(uri("C:\\src\\main.pop"), "file:///C:/src/main.pop");
+}
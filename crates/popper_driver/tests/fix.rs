@@ -0,0 +1,102 @@
+use popper_ast::ast::Span;
+use popper_driver::diff::unified_diff;
+use popper_driver::fix::apply_suggestions;
+use popper_error_core::Suggestion;
+
+fn suggestion(lo: usize, hi: usize, replacement: &str) -> Suggestion {
+    Suggestion {
+        message: String::new(),
+        span: Span::new(lo, hi),
+        replacement: replacement.to_string(),
+    }
+}
+
+#[test]
+fn test_apply_suggestions() {
+    let source = "let a = b;";
+    assert_eq!(apply_suggestions(source, vec![]), (source.to_string(), 0));
+    // Applied in source order, whatever the order they are given in.
+    assert_eq!(
+        apply_suggestions(source, vec![suggestion(8, 9, "c"), suggestion(4, 5, "_a")]),
+        ("let _a = c;".to_string(), 2)
+    );
+    assert_eq!(
+        apply_suggestions(source, vec![suggestion(10, 10, "\n"), suggestion(0, 0, "pub ")]),
+        ("pub let a = b;\n".to_string(), 2)
+    );
+
+    // The first of two overlapping suggestions wins.
+    assert_eq!(
+        apply_suggestions(source, vec![suggestion(6, 9, "= c"), suggestion(4, 7, "x =")]),
+        ("let x = b;".to_string(), 1)
+    );
+    // So does the first of two insertions at the same offset, but an insertion does not
+    // overlap a replacement starting there.
+    assert_eq!(
+        apply_suggestions(source, vec![suggestion(4, 4, "_"), suggestion(4, 4, "__")]),
+        ("let _a = b;".to_string(), 1)
+    );
+    assert_eq!(
+        apply_suggestions(source, vec![suggestion(4, 5, "c"), suggestion(4, 4, "_")]),
+        ("let _c = b;".to_string(), 2)
+    );
+    // Duplicates are applied once.
+    assert_eq!(
+        apply_suggestions(source, vec![suggestion(4, 5, "_a"), suggestion(4, 5, "_a")]),
+        ("let _a = b;".to_string(), 1)
+    );
+    // Suggestions outside of the source are ignored.
+    assert_eq!(
+        apply_suggestions(source, vec![suggestion(8, 12, "c")]),
+        (source.to_string(), 0)
+    );
+}
+
+#[test]
+fn test_unified_diff() {
+    assert_eq!(unified_diff("a.pop", "a\nb\n", "a\nb\n"), "");
+
+    let old: String = (1..=20).map(|line| format!("l{line}\n")).collect();
+    // Three lines of context around a change.
+    assert_eq!(
+        unified_diff("a.pop", &old, &old.replace("l10\n", "ten\n")),
+        "--- a.pop\n+++ a.pop\n@@ -7,7 +7,7 @@\n l7\n l8\n l9\n-l10\n+ten\n l11\n l12\n l13\n"
+    );
+    // Changes close enough for their contexts to touch share a hunk, others do not.
+    assert_eq!(
+        unified_diff("a.pop", &old, &old.replace("l3\n", "three\n").replace("l9\n", "nine\n")),
+        concat!(
+            "--- a.pop\n+++ a.pop\n@@ -1,12 +1,12 @@\n l1\n l2\n-l3\n+three\n l4\n l5\n l6\n l7\n l8\n",
+            "-l9\n+nine\n l10\n l11\n l12\n",
+        )
+    );
+    assert_eq!(
+        unified_diff("a.pop", &old, &old.replace("l2\n", "two\n").replace("l18\n", "")),
+        concat!(
+            "--- a.pop\n+++ a.pop\n@@ -1,5 +1,5 @@\n l1\n-l2\n+two\n l3\n l4\n l5\n",
+            "@@ -15,6 +15,5 @@\n l15\n l16\n l17\n-l18\n l19\n l20\n",
+        )
+    );
+    // An empty range starts at the line before it.
+    assert_eq!(unified_diff("a.pop", "", "a\n"), "--- a.pop\n+++ a.pop\n@@ -0,0 +1,1 @@\n+a\n");
+    assert_eq!(
+        unified_diff("a.pop", "a\nb\n", "a\nx\nb\n"),
+        "--- a.pop\n+++ a.pop\n@@ -1,2 +1,3 @@\n a\n+x\n b\n"
+    );
+}
+
+#[test]
+fn test_unified_diff_minimal() {
+    let lines = |text: &str| text.split(' ').map(|line| format!("{line}\n")).collect::<String>();
+    // The longest common subsequence, `c b a b` or `a b b a` among others, has 4 lines.
+    let diff = unified_diff("a.pop", &lines("a b c a b b a"), &lines("c b a b a c"));
+    let count = |prefix: char| diff.lines().skip(3).filter(|line| line.starts_with(prefix)).count();
+    assert_eq!((count(' '), count('-'), count('+')), (4, 3, 2));
+
+    // A change in a large file.
+    let old: String = (1..=5000).map(|line| format!("l{line}\n")).collect();
+    assert_eq!(
+        unified_diff("a.pop", &old, &old.replace("l2500\n", "")),
+        "--- a.pop\n+++ a.pop\n@@ -2497,7 +2497,6 @@\n l2497\n l2498\n l2499\n-l2500\n l2501\n l2502\n l2503\n"
+    );
+}
@@ -0,0 +1,72 @@
+use popper_driver::fmt::format_source;
+use popper_parser::Parser;
+use std::path::{Path, PathBuf};
+
+/// The example programs of the repository, and `hello.pop`.
+fn programs() -> Vec<PathBuf> {
+    fn visit(dir: &Path, programs: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                visit(&path, programs);
+            } else if path.extension().is_some_and(|extension| extension == "pop") {
+                programs.push(path);
+            }
+        }
+    }
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let mut programs = vec![root.join("hello.pop")];
+    visit(&root.join("examples"), &mut programs);
+    programs.sort();
+    programs
+}
+
+/// Checks that formatting `source` keeps its meaning and comments, and that formatting
+/// the result again changes nothing. Returns the formatted source.
+fn check_idempotent(name: &str, source: &str) -> String {
+    let formatted = format_source(source).unwrap_or_else(|err| panic!("cannot format {name}: {err:?}"));
+    assert_eq!(format_source(&formatted).unwrap(), formatted, "formatting {name} twice changes it");
+
+    let mut parser = Parser::new(source);
+    let mut formatted_parser = Parser::new(&formatted);
+    assert_eq!(
+        parser.parse().unwrap().dumps(),
+        formatted_parser.parse().unwrap().dumps(),
+        "formatting {name} changes its AST"
+    );
+    let comments: Vec<&str> = parser.comments().iter().map(|comment| comment.value.as_str()).collect();
+    let formatted_comments: Vec<&str> = formatted_parser.comments().iter().map(|comment| comment.value.as_str()).collect();
+    assert_eq!(comments, formatted_comments, "formatting {name} loses comments");
+    formatted
+}
+
+#[test]
+fn test_format_examples() {
+    let programs = programs();
+    assert!(programs.len() > 1);
+    for path in programs {
+        let source = std::fs::read_to_string(&path).unwrap();
+        let name = path.display().to_string();
+        let formatted = check_idempotent(&name, &source);
+        if path.starts_with(Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples")) {
+            assert_eq!(formatted, source, "{name} is not formatted");
+        }
+    }
+}
+
+#[test]
+fn test_format_style() {
+    let source = "// header\nfunc(C,allow( unused ))  f( a:int,b :string )->int=a+ -1*2 as int\n\n\n\nfunc main(){let x=f(1,\"s\");// x\nif x {}else{ // empty\n}\n  x;}\n";
+    let expected = "// header\nfunc(C, allow(unused)) f(a: int, b: string) -> int = a + -1 * 2 as int\n\nfunc main() {\n    let x = f(1, \"s\"); // x\n    if x {} else {\n        // empty\n    }\n    x;\n}\n";
+    assert_eq!(check_idempotent("the sample", source), expected);
+
+    let source = "// header\n\n\nmodule( allow(unused) ) ;module(deny(unreachable_code));// lints\nfunc main(){}\n";
+    let expected = "// header\n\nmodule(allow(unused), deny(unreachable_code)); // lints\nfunc main() {}\n";
+    assert_eq!(check_idempotent("module attributes", source), expected);
+
+    // A comment moved to the end of the line does not add a blank line after it.
+    let source = "func main() {\n    let x=add(1, // first\n  2);\n if true {}\n}\n";
+    let expected = "func main() {\n    let x = add(1, 2); // first\n    if true {}\n}\n";
+    assert_eq!(check_idempotent("comment inside a statement", source), expected);
+}
@@ -0,0 +1,116 @@
+use popper_context::Context;
+use popper_driver::{check, load_modules_with};
+use popper_error_core::lint::{LintLevel, LintLevels};
+use popper_error_core::{ErrorTable, Severity};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Checks `source` with the command-line lint `levels`, returning the message and
+/// severity of every diagnostic.
+fn check_source(source: &str, levels: &[(&str, LintLevel)]) -> Vec<(String, Severity)> {
+    let mut lints = LintLevels::new();
+    for (name, level) in levels {
+        lints.set(name, *level);
+    }
+    let mut context = Context::new();
+    let mut errors = ErrorTable::with_lints(lints);
+    let sources = HashMap::from([(PathBuf::from("main.pop"), source.to_string())]);
+    let modules = load_modules_with(&mut context, "main.pop", &sources, &mut errors).unwrap();
+    check(&context, &modules, &mut errors);
+    errors
+        .iter()
+        .map(|error| (error.diagnostics().message(), error.severity()))
+        .collect()
+}
+
+const SOURCE: &str = "func helper(n: int) {\n    let x = 1;\n}\n\nfunc main() {}\n";
+
+#[test]
+fn test_command_line_levels() {
+    use LintLevel::*;
+    use Severity::*;
+
+    assert_eq!(
+        check_source(SOURCE, &[]),
+        [
+            ("Unused variable `x`".to_string(), Warning),
+            ("Unused parameter `n`".to_string(), Warning),
+            ("Unused function `helper`".to_string(), Warning),
+        ]
+    );
+    assert!(check_source(SOURCE, &[("unused", Allow)]).is_empty());
+
+    // The last flag mentioning a lint wins, whether directly or through a group.
+    assert_eq!(
+        check_source(SOURCE, &[("unused", Allow), ("unused_parameters", Deny)]),
+        [("Unused parameter `n`".to_string(), Error)]
+    );
+    assert_eq!(
+        check_source(SOURCE, &[("unused_parameters", Deny), ("unused", Allow)]),
+        []
+    );
+    assert_eq!(
+        check_source(SOURCE, &[("warnings", Deny), ("unused_functions", Warn), ("unused_variables", Allow)]),
+        [
+            ("Unused parameter `n`".to_string(), Error),
+            ("Unused function `helper`".to_string(), Warning),
+        ]
+    );
+}
+
+#[test]
+fn test_deny_warnings() {
+    // `--deny-warnings` denies the `warnings` group.
+    let diagnostics = check_source(SOURCE, &[("warnings", LintLevel::Deny)]);
+    assert_eq!(diagnostics.len(), 3);
+    assert!(diagnostics.iter().all(|(_, severity)| *severity == Severity::Error));
+
+    let mut errors = ErrorTable::with_lints(LintLevels::new());
+    assert!(!errors.has_errors());
+    let mut context = Context::new();
+    let sources = HashMap::from([(PathBuf::from("main.pop"), SOURCE.to_string())]);
+    let modules = load_modules_with(&mut context, "main.pop", &sources, &mut errors).unwrap();
+    check(&context, &modules, &mut errors);
+    assert!(!errors.is_empty());
+    assert!(!errors.has_errors());
+}
+
+#[test]
+fn test_function_attribute() {
+    let source = "func(allow(unused)) helper(n: int) {\n    let x = 1;\n}\n\nfunc other(m: int) {}\n\nfunc main() {}\n";
+    assert_eq!(
+        check_source(source, &[]),
+        [
+            ("Unused parameter `m`".to_string(), Severity::Warning),
+            ("Unused function `other`".to_string(), Severity::Warning),
+        ]
+    );
+
+    // The attribute takes precedence over the command line.
+    let source = "func(deny(unused_variables)) helper() {\n    let x = 1;\n}\n\npub func main() = helper()\n";
+    assert_eq!(
+        check_source(source, &[("warnings", LintLevel::Allow)]),
+        [("Unused variable `x`".to_string(), Severity::Error)]
+    );
+}
+
+#[test]
+fn test_module_attribute() {
+    let source = format!("module(allow(unused));\n\n{SOURCE}");
+    assert!(check_source(&source, &[]).is_empty());
+
+    // A function attribute overrides the module one.
+    let source = "module(allow(unused));\n\nfunc(warn(unused_variables)) helper() {\n    let x = 1;\n}\n\nfunc main() {}\n";
+    assert_eq!(
+        check_source(source, &[]),
+        [("Unused variable `x`".to_string(), Severity::Warning)]
+    );
+
+    // Module attributes come first, and only take lints. After the first item, `module`
+    // is an identifier like any other.
+    let errors = check_source("func main() {}\nmodule(allow(unused));\n", &[]);
+    assert_eq!(errors[0].0, "Symbol `module` not found");
+    let errors = check_source("module(C);\n", &[]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].1, Severity::Error);
+}
@@ -0,0 +1,191 @@
+use popper_ast::file::FileId;
+use popper_context::Context;
+use popper_driver::{check, import_path, load_modules, Module};
+use popper_error_core::ErrorTable;
+use std::path::{Path, PathBuf};
+
+/// A directory of source files, removed when dropped.
+struct Fixture {
+    dir: PathBuf,
+}
+
+impl Fixture {
+    fn new(name: &str, files: &[(&str, &str)]) -> Fixture {
+        let dir = std::env::temp_dir().join(format!("popper-module-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (path, source) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, source).unwrap();
+        }
+        Fixture { dir }
+    }
+
+    fn path(&self, path: &str) -> String {
+        self.dir.join(path).display().to_string()
+    }
+
+    /// Loads and checks the program whose entry point is `main.pop`.
+    fn check(&self) -> (Context, Vec<Module>, ErrorTable) {
+        let mut context = Context::new();
+        let mut errors = ErrorTable::new();
+        let modules = load_modules(&mut context, &self.path("main.pop"), &mut errors).unwrap();
+        if errors.is_empty() {
+            check(&context, &modules, &mut errors);
+        }
+        (context, modules, errors)
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn file_id(context: &Context, path: &str) -> FileId {
+    context
+        .file_table()
+        .iter()
+        .find(|file| Path::new(file.info().path()) == Path::new(path))
+        .map(|file| file.id())
+        .unwrap()
+}
+
+/// The code and file of every diagnostic.
+fn diagnostics(errors: &ErrorTable) -> Vec<(u32, FileId)> {
+    errors.iter().map(|error| (error.diagnostics().code(), error.file())).collect()
+}
+
+#[test]
+fn test_import_resolution() {
+    let fixture = Fixture::new(
+        "resolution",
+        &[
+            ("main.pop", "import foo;\nimport foo.bar;\n\nfunc main() {\n    f(g());\n}\n"),
+            ("foo.pop", "pub func f(_x: int) {}\n"),
+            ("foo/bar.pop", "pub func g() -> int = 1\n"),
+        ],
+    );
+    let (context, modules, errors) = fixture.check();
+    assert!(errors.is_empty());
+
+    let main = file_id(&context, &fixture.path("main.pop"));
+    let foo = file_id(&context, &fixture.path("foo.pop"));
+    let bar = file_id(&context, &fixture.path("foo/bar.pop"));
+    let order: Vec<FileId> = modules.iter().map(|module| module.file).collect();
+    assert_eq!(order, [foo, bar, main]);
+    assert_eq!(modules[2].imports, [foo, bar]);
+}
+
+#[test]
+fn test_module_not_found() {
+    let fixture = Fixture::new("not-found", &[("main.pop", "func main() {}\nimport nothere;\n")]);
+    let (context, _, errors) = fixture.check();
+    let main = file_id(&context, &fixture.path("main.pop"));
+    assert_eq!(diagnostics(&errors), [(2001, main)]);
+
+    let error = errors.iter().next().unwrap().diagnostics();
+    let source = std::fs::read_to_string(fixture.path("main.pop")).unwrap();
+    assert_eq!(&source[error.span().lo..error.span().hi], "import nothere;");
+}
+
+#[test]
+fn test_import_cycle() {
+    let fixture = Fixture::new(
+        "cycle",
+        &[
+            ("main.pop", "import a;\nfunc main() {}\n"),
+            ("a.pop", "import b;\n"),
+            ("b.pop", "import a;\n"),
+        ],
+    );
+    let (context, _, errors) = fixture.check();
+    let b = file_id(&context, &fixture.path("b.pop"));
+    assert_eq!(diagnostics(&errors), [(2002, b)]);
+}
+
+#[test]
+fn test_nested_import() {
+    let fixture = Fixture::new(
+        "nested",
+        &[("main.pop", "func main() {\n    import foo;\n}\n"), ("foo.pop", "")],
+    );
+    let (context, modules, errors) = fixture.check();
+    let main = file_id(&context, &fixture.path("main.pop"));
+    assert_eq!(diagnostics(&errors), [(107, main)]);
+    assert!(modules.iter().all(|module| module.imports.is_empty()));
+}
+
+#[test]
+fn test_cross_file_errors() {
+    let fixture = Fixture::new(
+        "cross-file",
+        &[
+            ("main.pop", "import foo;\n\nfunc main() {\n    secret();\n}\n"),
+            ("foo.pop", "func secret() {}\npub func visible() = secret()\n"),
+        ],
+    );
+    let (context, _, errors) = fixture.check();
+    let main = file_id(&context, &fixture.path("main.pop"));
+    let foo = file_id(&context, &fixture.path("foo.pop"));
+    assert_eq!(diagnostics(&errors), [(1007, main)]);
+    // The definition is pointed at in the module defining it.
+    let error = errors.iter().next().unwrap().diagnostics();
+    let labels: Vec<Option<FileId>> = error.secondary_labels().iter().map(|label| label.file).collect();
+    assert_eq!(labels, [Some(foo)]);
+
+    // An error in an imported module is reported in that module.
+    let fixture = Fixture::new(
+        "imported-error",
+        &[
+            ("main.pop", "import foo;\n\nfunc main() {}\n"),
+            ("foo.pop", "pub let x = 1 + \"a\";\n"),
+        ],
+    );
+    let (context, _, errors) = fixture.check();
+    let foo = file_id(&context, &fixture.path("foo.pop"));
+    assert_eq!(diagnostics(&errors), [(1002, foo)]);
+}
+
+#[test]
+fn test_missing_import_hint() {
+    // `b` is loaded through `a`, but `main` does not import it.
+    let fixture = Fixture::new(
+        "missing-import",
+        &[
+            ("main.pop", "import a;\n\nfunc main() {\n    g();\n}\n"),
+            ("a.pop", "import b;\n\npub func f() = g()\n"),
+            ("b.pop", "pub func g() {}\n"),
+        ],
+    );
+    let (context, _, errors) = fixture.check();
+    let main = file_id(&context, &fixture.path("main.pop"));
+    assert_eq!(diagnostics(&errors), [(1001, main)]);
+    let error = errors.iter().next().unwrap().diagnostics();
+    assert_eq!(error.help().unwrap(), "`g` is defined in module `b`, add `import b;` to use it");
+
+    // The module is named by its path from the directory of the importing file.
+    let fixture = Fixture::new(
+        "missing-nested-import",
+        &[
+            ("main.pop", "import a;\n\nfunc main() {\n    g();\n}\n"),
+            ("a.pop", "import lib.b;\n\npub func f() = g()\n"),
+            ("lib/b.pop", "pub func g() {}\n"),
+        ],
+    );
+    let (_, _, errors) = fixture.check();
+    let error = errors.iter().next().unwrap().diagnostics();
+    assert_eq!(error.help().unwrap(), "`g` is defined in module `lib.b`, add `import lib.b;` to use it");
+}
+
+#[test]
+fn test_import_path() {
+    let path = |importer: &str, module: &str| import_path(Path::new(importer), Path::new(module));
+    assert_eq!(path("main.pop", "foo.pop"), Some("foo".to_string()));
+    assert_eq!(path("src/main.pop", "src/foo/bar/baz.pop"), Some("foo.bar.baz".to_string()));
+    assert_eq!(path("/home/me/src/main.pop", "/home/me/src/foo.pop"), Some("foo".to_string()));
+    // Modules outside of the directory of the importer cannot be imported.
+    assert_eq!(path("src/foo/bar.pop", "src/baz.pop"), None);
+    assert_eq!(path("src/main.pop", "lib/foo.pop"), None);
+}
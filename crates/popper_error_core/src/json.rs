@@ -0,0 +1,111 @@
+use crate::{registry, Error, ErrorTable};
+use popper_ast::ast::Span;
+use popper_ast::file::SourceFileInfo;
+use popper_context::Context;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    code: String,
+    severity: &'static str,
+    lint: Option<&'static str>,
+    message: String,
+    label: String,
+    note: Option<String>,
+    help: Option<String>,
+    file: &'a str,
+    span: JsonSpan,
+    secondary_labels: Vec<JsonLabel<'a>>,
+    suggestions: Vec<JsonSuggestion>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel<'a> {
+    message: String,
+    file: &'a str,
+    span: JsonSpan,
+}
+
+#[derive(Serialize)]
+struct JsonSuggestion {
+    message: String,
+    span: JsonSpan,
+    replacement: String,
+}
+
+/// A byte range with the 1-based line and column of both ends.
+#[derive(Serialize)]
+struct JsonSpan {
+    start: usize,
+    end: usize,
+    line_start: usize,
+    column_start: usize,
+    line_end: usize,
+    column_end: usize,
+}
+
+impl JsonSpan {
+    fn new(info: &SourceFileInfo, span: Span) -> JsonSpan {
+        let (line_start, column_start) = info.line_col(span.lo);
+        let (line_end, column_end) = info.line_col(span.hi);
+        JsonSpan {
+            start: span.lo,
+            end: span.hi,
+            line_start,
+            column_start,
+            line_end,
+            column_end,
+        }
+    }
+}
+
+impl Error {
+    /// Serializes the error as a single-line JSON object.
+    pub fn to_json(&self, context: &Context) -> Option<String> {
+        let info = context.get_file(self.info.file)?.info();
+        let mut secondary_labels = Vec::new();
+        for label in self.diagnostics.secondary_labels() {
+            let info = context.get_file(label.file.unwrap_or(self.info.file))?.info();
+            secondary_labels.push(JsonLabel {
+                message: label.message,
+                file: info.path(),
+                span: JsonSpan::new(info, label.span),
+            });
+        }
+        let suggestions = self
+            .diagnostics
+            .suggestions()
+            .into_iter()
+            .map(|suggestion| JsonSuggestion {
+                message: suggestion.message,
+                span: JsonSpan::new(info, suggestion.span),
+                replacement: suggestion.replacement,
+            })
+            .collect();
+        let diagnostic = JsonDiagnostic {
+            code: registry::code_name(self.diagnostics.code()),
+            severity: self.severity.name(),
+            lint: self.diagnostics.lint(),
+            message: self.diagnostics.message(),
+            label: self.diagnostics.label(),
+            note: self.diagnostics.note(),
+            help: self.diagnostics.help(),
+            file: info.path(),
+            span: JsonSpan::new(info, self.diagnostics.span()),
+            secondary_labels,
+            suggestions,
+        };
+        serde_json::to_string(&diagnostic).ok()
+    }
+}
+
+impl ErrorTable {
+    /// Prints every error as a JSON object, one per line.
+    pub fn print_json(&self, context: &Context) -> Result<(), String> {
+        for error in &self.errors {
+            let json = error.to_json(context).ok_or("file not found")?;
+            eprintln!("{json}");
+        }
+        Ok(())
+    }
+}
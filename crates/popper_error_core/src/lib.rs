@@ -1,4 +1,10 @@
-use ariadne::{Label, Report, Source};
+mod json;
+pub mod lint;
+pub mod registry;
+mod sarif;
+
+use ariadne::{Label, Report};
+use lint::{LintLevel, LintLevels};
 use popper_ast::{
     ast::{LineInfo, Span},
     file::FileId,
@@ -13,6 +19,85 @@ pub trait Diagnostics {
     fn note(&self) -> Option<String> {
         None
     }
+
+    fn help(&self) -> Option<String> {
+        None
+    }
+
+    fn secondary_labels(&self) -> Vec<SecondaryLabel> {
+        Vec::new()
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        Vec::new()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// The lint controlling this diagnostic, for warnings whose level can be changed.
+    fn lint(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Whether a diagnostic stops the compilation or is only reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// How the diagnostics of an `ErrorTable` are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// Rendered with the source snippets, for humans.
+    #[default]
+    Human,
+    /// One JSON object per line.
+    Json,
+    /// A SARIF 2.1.0 log, for code scanning tools.
+    Sarif,
+}
+
+impl ErrorFormat {
+    pub fn from_name(name: &str) -> Option<ErrorFormat> {
+        match name {
+            "human" => Some(ErrorFormat::Human),
+            "json" => Some(ErrorFormat::Json),
+            "sarif" => Some(ErrorFormat::Sarif),
+            _ => None,
+        }
+    }
+}
+
+/// An extra span shown next to the main one, possibly in another file.
+#[derive(Debug, Clone)]
+pub struct SecondaryLabel {
+    /// `None` when the span is in the same file as the error.
+    pub file: Option<FileId>,
+    pub span: Span,
+    pub message: String,
+}
+
+/// A replacement of the source fixing the diagnostic, in the file of the diagnostic.
+/// Suggestions are machine-applicable: applying them as is must give valid code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub message: String,
+    /// The text to replace, empty for an insertion.
+    pub span: Span,
+    pub replacement: String,
 }
 
 pub struct ErrorInfo {
@@ -36,6 +121,10 @@ impl ErrorInfo {
 
 pub struct Error {
     info: ErrorInfo,
+    severity: Severity,
+    /// Level set by an attribute where the diagnostic was reported, which takes
+    /// precedence over the levels of the `ErrorTable`.
+    lint_level: Option<LintLevel>,
     diagnostics: Box<dyn Diagnostics>,
 }
 
@@ -43,64 +132,160 @@ impl Error {
     pub fn new<T: Diagnostics + 'static>(info: ErrorInfo, diagnostics: T) -> Error {
         Error {
             info,
+            severity: diagnostics.severity(),
+            lint_level: None,
             diagnostics: Box::new(diagnostics),
         }
     }
 
-    pub fn report<'a>(
+    pub fn with_lint_level(mut self, level: Option<LintLevel>) -> Error {
+        self.lint_level = level;
+        self
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn file(&self) -> FileId {
+        self.info.file
+    }
+
+    pub fn diagnostics(&self) -> &dyn Diagnostics {
+        self.diagnostics.as_ref()
+    }
+
+    pub fn report(
         &self,
-        filename: &'a str,
-    ) -> Option<Report<(&'a str, std::ops::Range<usize>)>> {
+        context: &popper_context::Context,
+    ) -> Option<Report<'static, (String, std::ops::Range<usize>)>> {
+        let path = |file: FileId| {
+            context
+                .get_file(file)
+                .map(|file| file.info().absolute_path().to_string())
+        };
         let span = self.diagnostics.span();
-        let span = (filename, span.lo..span.hi);
+        let span = (path(self.info.file)?, span.lo..span.hi);
         let message = self.diagnostics.message();
         let label = self.diagnostics.label();
         let code = self.diagnostics.code();
-        let report = Report::build(ariadne::ReportKind::Error, span.clone())
-            .with_code(code)
+        let kind = match self.severity {
+            Severity::Error => ariadne::ReportKind::Error,
+            Severity::Warning => ariadne::ReportKind::Warning,
+        };
+        let mut report = Report::build(kind, span.clone())
+            .with_code(registry::code_name(code))
             .with_message(message)
-            .with_label(Label::new(span).with_message(label))
-            .finish();
-        Some(report)
+            .with_label(Label::new(span).with_message(label));
+        for secondary in self.diagnostics.secondary_labels() {
+            let file = path(secondary.file.unwrap_or(self.info.file))?;
+            let span = (file, secondary.span.lo..secondary.span.hi);
+            report = report.with_label(Label::new(span).with_message(secondary.message));
+        }
+        for suggestion in self.diagnostics.suggestions() {
+            let span = (path(self.info.file)?, suggestion.span.lo..suggestion.span.hi);
+            let message = format!("{}: `{}`", suggestion.message, suggestion.replacement);
+            report = report.with_label(Label::new(span).with_message(message));
+        }
+        if let Some(note) = self.diagnostics.note() {
+            report = report.with_note(note);
+        }
+        if let Some(help) = self.diagnostics.help() {
+            report = report.with_help(help);
+        }
+        Some(report.finish())
     }
 
     pub fn print(&self, context: popper_context::Context) -> Result<(), String> {
-        let file = context.get_file(self.info.file);
-        if let Some(file) = file {
-            if let Some(report) = self.report(file.info().absolute_path()) {
-                report
-                    .eprint((
-                        file.info().absolute_path(),
-                        Source::from(file.info().source()),
-                    ))
-                    .map_err(|x| x.to_string())?;
-                Ok(())
-            } else {
-                Err("report not found".to_string())
-            }
-        } else {
-            Err("file not found".to_string())
+        if context.get_file(self.info.file).is_none() {
+            return Err("file not found".to_string());
         }
+        let report = self.report(&context).ok_or("report not found")?;
+        let sources = context.file_table().iter().map(|file| {
+            (
+                file.info().absolute_path().to_string(),
+                file.info().source().to_string(),
+            )
+        });
+        report
+            .eprint(ariadne::sources(sources))
+            .map_err(|x| x.to_string())
     }
 }
 
 pub struct ErrorTable {
     errors: Vec<Error>,
+    lints: LintLevels,
 }
 
 impl ErrorTable {
     pub fn new() -> ErrorTable {
-        ErrorTable { errors: Vec::new() }
+        ErrorTable::with_lints(LintLevels::new())
     }
 
-    pub fn add_error(&mut self, error: Error) {
+    pub fn with_lints(lints: LintLevels) -> ErrorTable {
+        ErrorTable {
+            errors: Vec::new(),
+            lints,
+        }
+    }
+
+    /// Adds `error`, applying its lint level: allowed lints are dropped and denied ones
+    /// are turned into errors.
+    pub fn add_error(&mut self, mut error: Error) {
+        if let Some(lint) = error.diagnostics.lint() {
+            match error.lint_level.or_else(|| self.lints.level(lint)) {
+                Some(LintLevel::Allow) => return,
+                Some(LintLevel::Warn) => error.severity = Severity::Warning,
+                Some(LintLevel::Deny) => error.severity = Severity::Error,
+                None => {}
+            }
+        }
         self.errors.push(error);
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Error> {
+        self.errors.iter()
+    }
+
+    /// Returns `true` if at least one diagnostic is an error rather than a warning.
+    pub fn has_errors(&self) -> bool {
+        self.errors
+            .iter()
+            .any(|error| error.severity() == Severity::Error)
+    }
+
+    /// The suggestions of every diagnostic, with the file they apply to.
+    pub fn suggestions(&self) -> Vec<(FileId, Suggestion)> {
+        self.errors
+            .iter()
+            .flat_map(|error| {
+                let file = error.info.file;
+                error
+                    .diagnostics
+                    .suggestions()
+                    .into_iter()
+                    .map(move |suggestion| (file, suggestion))
+            })
+            .collect()
+    }
+
     pub fn print(&self, context: popper_context::Context) -> Result<(), String> {
         for error in &self.errors {
             error.print(context.clone())?;
         }
         Ok(())
     }
+
+    pub fn emit(&self, context: popper_context::Context, format: ErrorFormat) -> Result<(), String> {
+        match format {
+            ErrorFormat::Human => self.print(context),
+            ErrorFormat::Json => self.print_json(&context),
+            ErrorFormat::Sarif => self.print_sarif(&context),
+        }
+    }
 }
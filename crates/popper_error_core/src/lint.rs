@@ -0,0 +1,57 @@
+pub use popper_ast::attribute::LintLevel;
+
+pub const UNUSED_VARIABLES: &str = "unused_variables";
+pub const UNUSED_PARAMETERS: &str = "unused_parameters";
+pub const UNUSED_FUNCTIONS: &str = "unused_functions";
+pub const UNREACHABLE_CODE: &str = "unreachable_code";
+
+/// Every lint a warning can be reported under.
+pub const LINTS: &[&str] = &[
+    UNUSED_VARIABLES,
+    UNUSED_PARAMETERS,
+    UNUSED_FUNCTIONS,
+    UNREACHABLE_CODE,
+];
+
+/// Names standing for several lints at once. `warnings` covers every lint.
+pub const LINT_GROUPS: &[(&str, &[&str])] = &[
+    ("unused", &[UNUSED_VARIABLES, UNUSED_PARAMETERS, UNUSED_FUNCTIONS]),
+    ("warnings", LINTS),
+];
+
+/// Returns `true` if `name` is a lint or a lint group.
+pub fn is_known(name: &str) -> bool {
+    LINTS.contains(&name) || LINT_GROUPS.iter().any(|(group, _)| *group == name)
+}
+
+/// Returns `true` if `name` designates `lint`, either directly or through a group.
+pub fn matches(name: &str, lint: &str) -> bool {
+    name == lint
+        || LINT_GROUPS
+            .iter()
+            .any(|(group, lints)| *group == name && lints.contains(&lint))
+}
+
+/// Lint levels set on the command line, the last matching entry wins.
+#[derive(Debug, Clone, Default)]
+pub struct LintLevels {
+    levels: Vec<(String, LintLevel)>,
+}
+
+impl LintLevels {
+    pub fn new() -> LintLevels {
+        LintLevels::default()
+    }
+
+    pub fn set(&mut self, name: &str, level: LintLevel) {
+        self.levels.push((name.to_string(), level));
+    }
+
+    pub fn level(&self, lint: &str) -> Option<LintLevel> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|(name, _)| matches(name, lint))
+            .map(|(_, level)| *level)
+    }
+}
@@ -0,0 +1,100 @@
+use popper_error_macro::error_codes;
+
+/// A registered error code with the diagnostic type reporting it.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorCode {
+    pub code: u32,
+    /// `<crate>::<type>` of the diagnostic owning the code.
+    pub owner: &'static str,
+    /// Long-form explanation, in markdown, shown by `popper --explain`.
+    pub explanation: &'static str,
+}
+
+impl ErrorCode {
+    /// The first paragraph of the explanation, on one line.
+    pub fn summary(&self) -> String {
+        let paragraph = self.explanation.split("\n\n").next().unwrap_or_default();
+        paragraph.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+// Lexer errors are E00xx, parser errors E01xx, semantic errors E1xxx and driver
+// errors E2xxx.
+error_codes! {
+    E0001: popper_lexer::UnexpectedToken,
+    E0002: popper_lexer::UnterminatedChar,
+    E0003: popper_lexer::InvalidChar,
+    E0004: popper_lexer::InvalidEscape,
+    E0005: popper_lexer::UnterminatedString,
+    E0006: popper_lexer::InvalidIntLiteral,
+
+    E0101: popper_parser::UnexpectedToken,
+    E0102: popper_parser::ExpectedValidAttribute,
+    E0103: popper_parser::IntLiteralOutOfRange,
+    E0104: popper_parser::VisibilityNotAllowed,
+    E0105: popper_parser::UnknownLint,
+    E0106: popper_parser::MissingSemicolon,
+    E0107: popper_parser::ImportNotAllowed,
+
+    E1001: popper_semantic_analyzer::SymbolNotFound,
+    E1002: popper_semantic_analyzer::TypeMismatch,
+    E1003: popper_semantic_analyzer::NotAFunction,
+    E1004: popper_semantic_analyzer::ArgumentCountMismatch,
+    E1005: popper_semantic_analyzer::ReturnNotInFunction,
+    E1006: popper_semantic_analyzer::InvalidCast,
+    E1007: popper_semantic_analyzer::SymbolIsPrivate,
+    E1008: popper_semantic_analyzer::DuplicateDefinition,
+    E1009: popper_semantic_analyzer::MissingReturn,
+    E1010: popper_semantic_analyzer::UnreachableCode,
+    E1011: popper_semantic_analyzer::UnusedVariable,
+    E1012: popper_semantic_analyzer::UnusedParameter,
+    E1013: popper_semantic_analyzer::UnusedFunction,
+
+    E2001: popper_driver::ModuleNotFound,
+    E2002: popper_driver::ImportCycle,
+}
+
+/// The name of an error code as shown to the user, e.g. `E1001`.
+pub fn code_name(code: u32) -> String {
+    format!("E{code:04}")
+}
+
+/// Parses `E1001` or `1001`.
+pub fn parse_code(name: &str) -> Option<u32> {
+    name.strip_prefix('E').unwrap_or(name).parse().ok()
+}
+
+pub fn lookup(code: u32) -> Option<&'static ErrorCode> {
+    ERROR_CODES.iter().find(|error_code| error_code.code == code)
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Fails the compilation when `code` is not registered for `owner`. Called in a
+/// `const` item by `#[derive(Diagnostics)]`, and by hand for manual implementations.
+pub const fn check_code(code: u32, owner: &str) {
+    let mut i = 0;
+    while i < ERROR_CODES.len() {
+        if ERROR_CODES[i].code == code {
+            if !str_eq(ERROR_CODES[i].owner, owner) {
+                panic!("error code is registered for another diagnostic in popper_error_core::registry");
+            }
+            return;
+        }
+        i += 1;
+    }
+    panic!("error code is not registered in popper_error_core::registry");
+}
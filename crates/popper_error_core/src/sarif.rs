@@ -0,0 +1,149 @@
+use crate::{registry, Error, ErrorTable};
+use popper_ast::ast::Span;
+use popper_ast::file::SourceFileInfo;
+use popper_context::Context;
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The URI of the file at `path`: a `file://` URI if the path is absolute, a relative
+/// reference otherwise, with `/` separators and the other reserved bytes percent-encoded.
+fn artifact_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut uri = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => uri.push(byte as char),
+            b':' if uri.len() == 1 => uri.push(':'),
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    let bytes = path.as_bytes();
+    if path.starts_with('/') {
+        format!("file://{uri}")
+    } else if bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'/' {
+        format!("file:///{uri}")
+    } else {
+        uri
+    }
+}
+
+fn location(info: &SourceFileInfo, span: Span) -> Value {
+    let (start_line, start_column) = info.line_col(span.lo);
+    let (end_line, end_column) = info.line_col(span.hi);
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": artifact_uri(info.path()) },
+            "region": {
+                "startLine": start_line,
+                "startColumn": start_column,
+                "endLine": end_line,
+                "endColumn": end_column,
+                "byteOffset": span.lo,
+                "byteLength": span.hi - span.lo,
+            },
+        },
+    })
+}
+
+impl Error {
+    fn to_sarif_result(&self, context: &Context, rule_index: usize) -> Option<Value> {
+        let info = context.get_file(self.info.file)?.info();
+        let mut related_locations = Vec::new();
+        for (id, label) in self.diagnostics.secondary_labels().into_iter().enumerate() {
+            let info = context.get_file(label.file.unwrap_or(self.info.file))?.info();
+            let mut related = location(info, label.span);
+            related["id"] = json!(id);
+            related["message"] = json!({ "text": label.message });
+            related_locations.push(related);
+        }
+        let fixes: Vec<Value> = self
+            .diagnostics
+            .suggestions()
+            .into_iter()
+            .map(|suggestion| {
+                let region = location(info, suggestion.span)["physicalLocation"]["region"].take();
+                json!({
+                    "description": { "text": suggestion.message },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": artifact_uri(info.path()) },
+                        "replacements": [{
+                            "deletedRegion": region,
+                            "insertedContent": { "text": suggestion.replacement },
+                        }],
+                    }],
+                })
+            })
+            .collect();
+        let mut message = self.diagnostics.message();
+        for text in [self.diagnostics.note(), self.diagnostics.help()].into_iter().flatten() {
+            message.push('\n');
+            message.push_str(&text);
+        }
+        Some(json!({
+            "ruleId": registry::code_name(self.diagnostics.code()),
+            "ruleIndex": rule_index,
+            "level": self.severity.name(),
+            "message": { "text": message },
+            "locations": [location(info, self.diagnostics.span())],
+            "relatedLocations": related_locations,
+            "fixes": fixes,
+        }))
+    }
+}
+
+impl ErrorTable {
+    /// Builds a SARIF 2.1.0 log with one rule per error code and one result per error.
+    /// A rule is described by the explanation of its code, a result by the message of
+    /// its error followed by its note and help.
+    pub fn to_sarif(&self, context: &Context) -> Option<Value> {
+        let mut codes = Vec::new();
+        let mut rules = Vec::new();
+        let mut results = Vec::new();
+        for error in &self.errors {
+            let code = error.diagnostics.code();
+            let rule_index = match codes.iter().position(|&c| c == code) {
+                Some(index) => index,
+                None => {
+                    let mut rule = json!({ "id": registry::code_name(code) });
+                    if let Some(lint) = error.diagnostics.lint() {
+                        rule["name"] = json!(lint);
+                    }
+                    // The same for every result of the rule, unlike the note of a diagnostic.
+                    if let Some(error_code) = registry::lookup(code) {
+                        rule["shortDescription"] = json!({ "text": error_code.summary() });
+                        rule["help"] = json!({ "text": error_code.explanation, "markdown": error_code.explanation });
+                    }
+                    codes.push(code);
+                    rules.push(rule);
+                    rules.len() - 1
+                }
+            };
+            results.push(error.to_sarif_result(context, rule_index)?);
+        }
+
+        Some(json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "popper",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        }))
+    }
+
+    /// Prints the SARIF log to stdout, so that it can be redirected to a file.
+    pub fn print_sarif(&self, context: &Context) -> Result<(), String> {
+        let sarif = self.to_sarif(context).ok_or("file not found")?;
+        let sarif = serde_json::to_string_pretty(&sarif).map_err(|err| err.to_string())?;
+        println!("{sarif}");
+        Ok(())
+    }
+}
@@ -19,7 +19,27 @@ impl Parse for ExprList {
 
 /// Derive the `Diagnostic` trait for a struct.
 /// require "message" , "code"
-#[proc_macro_derive(Diagnostics, attributes(message, code, label, span, note))]
+///
+/// Fields can carry `#[secondary_span]` (or `#[secondary_span(file = <field>)]` for a span
+/// in another file) with a `#[secondary_label = "..."]`, and
+/// `#[suggestion(message = "...", replacement = "...")]`. These fields are either a
+/// `Span` or an `Option<Span>`.
+#[proc_macro_derive(
+    Diagnostics,
+    attributes(
+        message,
+        code,
+        label,
+        span,
+        note,
+        help,
+        warning,
+        lint,
+        secondary_span,
+        secondary_label,
+        suggestion
+    )
+)]
 pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
@@ -71,7 +91,7 @@ pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
         })
         .expect("message attribute is required");
 
-    let code = input
+    let code: u32 = input
         .attrs
         .iter()
         .find_map(|attr| {
@@ -88,7 +108,9 @@ pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
                 None
             }
         })
-        .expect("code attribute is required");
+        .expect("code attribute is required")
+        .parse()
+        .expect("code attribute must be an integer");
 
     let label = input
         .attrs
@@ -137,10 +159,66 @@ pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
         }
     });
 
+    let help = input.attrs.iter().find_map(|attr| {
+        if attr.path().is_ident("help") {
+            match attr.meta.clone() {
+                Meta::NameValue(MetaNameValue {
+                    value: Expr::Lit(lit),
+                    ..
+                }) => Some(lit),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    });
+
+    let secondary_labels = match fields
+        .iter()
+        .map(|field| secondary_label(field))
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(labels) => labels.into_iter().flatten().collect::<Vec<_>>(),
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let suggestions = match fields
+        .iter()
+        .map(|field| suggestion(field))
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(suggestions) => suggestions.into_iter().flatten().collect::<Vec<_>>(),
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     let span = quote! {
         self.#span
     };
 
+    let lint = input
+        .attrs
+        .iter()
+        .find_map(|attr| {
+            if attr.path().is_ident("lint") {
+                match attr.meta.clone() {
+                    Meta::NameValue(MetaNameValue {
+                        value: Expr::Lit(lit),
+                        ..
+                    }) => Some(quote! { Some(#lit) }),
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        })
+        .unwrap_or(quote! { None });
+
+    let severity = if input.attrs.iter().any(|attr| attr.path().is_ident("warning")) {
+        quote! { popper_error_core::Severity::Warning }
+    } else {
+        quote! { popper_error_core::Severity::Error }
+    };
+
     let new_fields: Vec<_> = fields
         .iter()
         .map(|field| {
@@ -161,7 +239,22 @@ pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
         })
         .unwrap_or(quote! { None });
 
+    let help = help
+        .map(|lit| {
+            let lit = lit.lit;
+            quote! {
+                #(#new_fields)*
+                Some(format!(#lit))
+            }
+        })
+        .unwrap_or(quote! { None });
+
     let expanded = quote! {
+        const _: () = popper_error_core::registry::check_code(
+            #code,
+            concat!(env!("CARGO_CRATE_NAME"), "::", stringify!(#name)),
+        );
+
         impl Diagnostics for #name {
             fn message(&self) -> String {
                 #(#new_fields)*
@@ -169,8 +262,7 @@ pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
             }
 
             fn code(&self) -> u32 {
-                #(#new_fields)*
-                format!(#code).parse().unwrap()
+                #code
             }
 
             fn label(&self) -> String {
@@ -185,6 +277,35 @@ pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
             fn note(&self) -> Option<String> {
                 #note
             }
+
+            #[allow(unused_variables)]
+            fn help(&self) -> Option<String> {
+                #help
+            }
+
+            #[allow(unused_variables)]
+            fn secondary_labels(&self) -> Vec<popper_error_core::SecondaryLabel> {
+                #(#new_fields)*
+                let mut labels = Vec::new();
+                #(#secondary_labels)*
+                labels
+            }
+
+            #[allow(unused_variables)]
+            fn suggestions(&self) -> Vec<popper_error_core::Suggestion> {
+                #(#new_fields)*
+                let mut suggestions = Vec::new();
+                #(#suggestions)*
+                suggestions
+            }
+
+            fn severity(&self) -> popper_error_core::Severity {
+                #severity
+            }
+
+            fn lint(&self) -> Option<&'static str> {
+                #lint
+            }
         }
     };
 
@@ -192,6 +313,87 @@ pub fn derive_diagnostics(input: TokenStream) -> TokenStream {
 }
 
 
+/// Builds the code pushing the secondary label of `field` to `labels`, if the field has
+/// a `#[secondary_span]`.
+fn secondary_label(field: &Field) -> Result<Option<proc_macro2::TokenStream>> {
+    let Some(attr) = field.attrs.iter().find(|attr| attr.path().is_ident("secondary_span")) else {
+        return Ok(None);
+    };
+    let ident = &field.ident;
+
+    let mut file = quote! { None };
+    if let Meta::List(_) = attr.meta {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("file") {
+                let field: Ident = meta.value()?.parse()?;
+                file = quote! { Option::<popper_ast::file::FileId>::from(self.#field) };
+                Ok(())
+            } else {
+                Err(meta.error("expected `file = <field>`"))
+            }
+        })?;
+    }
+
+    let label = field
+        .attrs
+        .iter()
+        .find_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                path,
+                value: Expr::Lit(lit),
+                ..
+            }) if path.is_ident("secondary_label") => Some(lit.lit.clone()),
+            _ => None,
+        })
+        .ok_or_else(|| Error::new_spanned(attr, "`#[secondary_span]` requires a `#[secondary_label = \"...\"]`"))?;
+
+    Ok(Some(quote! {
+        if let Some(span) = Option::<popper_ast::ast::Span>::from(self.#ident) {
+            labels.push(popper_error_core::SecondaryLabel {
+                file: #file,
+                span,
+                message: format!(#label),
+            });
+        }
+    }))
+}
+
+/// Builds the code pushing the suggestion of `field` to `suggestions`, if the field
+/// has a `#[suggestion(message = "...", replacement = "...")]`.
+fn suggestion(field: &Field) -> Result<Option<proc_macro2::TokenStream>> {
+    let Some(attr) = field.attrs.iter().find(|attr| attr.path().is_ident("suggestion")) else {
+        return Ok(None);
+    };
+    let ident = &field.ident;
+
+    let mut message = None;
+    let mut replacement = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("message") {
+            message = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        } else if meta.path.is_ident("replacement") {
+            replacement = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `message` or `replacement`"))
+        }
+    })?;
+    let (Some(message), Some(replacement)) = (message, replacement) else {
+        return Err(Error::new_spanned(attr, "`#[suggestion]` requires a `message` and a `replacement`"));
+    };
+
+    Ok(Some(quote! {
+        if let Some(span) = Option::<popper_ast::ast::Span>::from(self.#ident) {
+            suggestions.push(popper_error_core::Suggestion {
+                message: format!(#message),
+                span,
+                replacement: format!(#replacement),
+            });
+        }
+    }))
+}
+
 fn generate_enum_input(input: &DeriveInput) -> TokenStream {
     let name = &input.ident;
     let variants = match input.data {
@@ -244,9 +446,110 @@ fn generate_enum_input(input: &DeriveInput) -> TokenStream {
                     #(#m => _0.note(),)*
                 }
             }
+
+            fn help(&self) -> Option<String> {
+                match self {
+                    #(#m => _0.help(),)*
+                }
+            }
+
+            fn secondary_labels(&self) -> Vec<popper_error_core::SecondaryLabel> {
+                match self {
+                    #(#m => _0.secondary_labels(),)*
+                }
+            }
+
+            fn suggestions(&self) -> Vec<popper_error_core::Suggestion> {
+                match self {
+                    #(#m => _0.suggestions(),)*
+                }
+            }
+
+            fn severity(&self) -> popper_error_core::Severity {
+                match self {
+                    #(#m => _0.severity(),)*
+                }
+            }
+
+            fn lint(&self) -> Option<&'static str> {
+                match self {
+                    #(#m => _0.lint(),)*
+                }
+            }
         }
     };
     
     expanded.into()
 
 }
+
+struct ErrorCodeEntry {
+    code: Ident,
+    owner: Path,
+}
+
+impl Parse for ErrorCodeEntry {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let code = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let owner = input.parse()?;
+        Ok(ErrorCodeEntry { code, owner })
+    }
+}
+
+/// Declares the error code registry: `E1001: popper_semantic_analyzer::SymbolNotFound, ...`.
+/// Each code must be unique, owned by a single diagnostic type and explained in
+/// `codes/<code>.md`, relative to the file invoking the macro.
+#[proc_macro]
+pub fn error_codes(input: TokenStream) -> TokenStream {
+    let entries = parse_macro_input!(input with Punctuated::<ErrorCodeEntry, Token![,]>::parse_terminated);
+
+    let mut codes: Vec<u32> = Vec::new();
+    let mut owners: Vec<String> = Vec::new();
+    let mut items = Vec::new();
+    for entry in entries {
+        let name = entry.code.to_string();
+        let code = match name.strip_prefix('E').filter(|digits| digits.len() == 4) {
+            Some(digits) => digits.parse::<u32>().ok(),
+            None => None,
+        };
+        let Some(code) = code else {
+            return Error::new(entry.code.span(), "error codes are written `E` followed by 4 digits")
+                .to_compile_error()
+                .into();
+        };
+        if codes.contains(&code) {
+            return Error::new(entry.code.span(), format!("error code `{name}` is registered twice"))
+                .to_compile_error()
+                .into();
+        }
+        let owner = entry
+            .owner
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+        if owners.contains(&owner) {
+            return Error::new(entry.code.span(), format!("`{owner}` already has an error code"))
+                .to_compile_error()
+                .into();
+        }
+        codes.push(code);
+        owners.push(owner.clone());
+
+        let explanation = format!("../codes/{name}.md");
+        items.push(quote! {
+            ErrorCode {
+                code: #code,
+                owner: #owner,
+                explanation: include_str!(#explanation),
+            }
+        });
+    }
+
+    let expanded = quote! {
+        pub const ERROR_CODES: &[ErrorCode] = &[#(#items),*];
+    };
+    expanded.into()
+}
@@ -3,12 +3,17 @@ use std::iter::Peekable;
 use std::str::Chars;
 use popper_ast::ast::Span;
 use popper_ast::token::{Token, TokenKind};
+use crate::error::LexerError;
 
 #[derive(Debug, Clone)]
 pub struct Cursor {
     string: String,
     pos: usize,
     remaining: usize,
+    /// The `//` comments lexed so far, which are skipped like whitespace.
+    comments: Vec<Token>,
+    /// Whether whitespace, newlines and comments are returned as tokens, see `lossless`.
+    lossless: bool,
 }
 
 impl Cursor {
@@ -18,6 +23,19 @@ impl Cursor {
             string: input.to_string(),
             pos: 0,
             remaining,
+            comments: Vec::new(),
+            lossless: false,
+        }
+    }
+
+    /// A cursor returning the whitespace, newlines and comments as `Whitespace`,
+    /// `Newline` and `Comment` tokens instead of skipping them. The value of every token
+    /// is its text as written, e.g. with the quotes and escapes of a string, so that the
+    /// values of the tokens add up to the input.
+    pub fn lossless(input: &str) -> Self {
+        Cursor {
+            lossless: true,
+            ..Cursor::new(input)
         }
     }
     
@@ -81,6 +99,15 @@ impl Cursor {
         
     }
 
+    pub fn source(&self) -> &str {
+        &self.string
+    }
+
+    /// The comments lexed so far, in order.
+    pub fn comments(&self) -> &[Token] {
+        &self.comments
+    }
+
     pub fn pos(&self) -> usize {
         self.pos
     }
@@ -89,8 +116,171 @@ impl Cursor {
         self.remaining
     }
 
+    /// Reads the escape sequence following a `\`, which has already been consumed.
+    fn scan_escape(&mut self) -> super::Result<char> {
+        let start = self.pos - 1;
+        let c = match self.next() {
+            Some(c) => c,
+            None => return Err(LexerError::invalid_escape("\\".to_string(), Span::new(start, self.pos))),
+        };
+        let value = match c {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            '\\' => Some('\\'),
+            '\'' => Some('\''),
+            '"' => Some('"'),
+            'x' => {
+                let digits = self.take_while(|c| c.is_ascii_hexdigit(), false);
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|value| digits.len() == 2 && *value <= 0x7F)
+                    .and_then(char::from_u32)
+            }
+            'u' => {
+                if self.peek() == Some('{') {
+                    self.next();
+                    let digits = self.take_while(|c| c.is_ascii_hexdigit(), false);
+                    if self.peek() == Some('}') {
+                        self.next();
+                        u32::from_str_radix(&digits, 16)
+                            .ok()
+                            .filter(|_| (1..=6).contains(&digits.len()))
+                            .and_then(char::from_u32)
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        value.ok_or_else(|| {
+            LexerError::invalid_escape(self.string[start..self.pos].to_string(), Span::new(start, self.pos))
+        })
+    }
+
+    fn lex_char(&mut self, start: usize) -> super::Result<Token> {
+        self.next();
+        let value = match self.next() {
+            None | Some('\n') => return Err(LexerError::unterminated_char(Span::new(start, start + 1))),
+            Some('\'') => {
+                return Err(LexerError::invalid_char(
+                    "''".to_string(),
+                    "empty character literal".to_string(),
+                    Span::new(start, self.pos),
+                ))
+            }
+            Some('\\') => self.scan_escape()?,
+            Some(c) => c,
+        };
+
+        match self.next() {
+            Some('\'') => Ok(Token::new(TokenKind::Char, Span::new(start, self.pos), value.to_string())),
+            None | Some('\n') => Err(LexerError::unterminated_char(Span::new(start, start + 1))),
+            Some(_) => {
+                self.take_while(|c| c != '\'' && c != '\n', false);
+                if self.peek() != Some('\'') {
+                    return Err(LexerError::unterminated_char(Span::new(start, start + 1)));
+                }
+                self.next();
+                Err(LexerError::invalid_char(
+                    self.string[start..self.pos].to_string(),
+                    "more than one character".to_string(),
+                    Span::new(start, self.pos),
+                ))
+            }
+        }
+    }
+
+    /// Lexes an integer literal such as `42`, `1_000`, `0xFF`, `0o755` or `0b1010`.
+    /// The token keeps the literal as written, the parser computes its value.
+    fn lex_number(&mut self, start: usize) -> super::Result<Token> {
+        let (radix, base) = match self.string[self.pos..].get(..2) {
+            Some("0x" | "0X") => (16, "hexadecimal"),
+            Some("0o" | "0O") => (8, "octal"),
+            Some("0b" | "0B") => (2, "binary"),
+            _ => (10, "decimal"),
+        };
+        if radix != 10 {
+            self.take(2, false);
+        }
+        let digits_start = self.pos;
+        let digits = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_', false);
+        let literal = self.string[start..self.pos].to_string();
+
+        if let Some((offset, digit)) = digits.char_indices().find(|(_, c)| *c != '_' && !c.is_digit(radix)) {
+            let lo = digits_start + offset;
+            return Err(LexerError::invalid_int_literal(
+                literal,
+                format!("invalid digit `{digit}` in {base} literal"),
+                Span::new(lo, lo + digit.len_utf8()),
+            ));
+        }
+        if !digits.chars().any(|c| c != '_') {
+            return Err(LexerError::invalid_int_literal(
+                literal.clone(),
+                format!("no digits after the `{}` prefix", &literal[..2]),
+                Span::new(start, self.pos),
+            ));
+        }
+
+        Ok(Token::new(TokenKind::Number, Span::new(start, self.pos), literal))
+    }
+
+    fn lex_string(&mut self, start: usize) -> super::Result<Token> {
+        self.next();
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => break,
+                Some('\\') if self.peek().is_some() => value.push(self.scan_escape()?),
+                Some('\\') | None => return Err(LexerError::unterminated_string(Span::new(start, start + 1))),
+                Some(c) => value.push(c),
+            }
+        }
+
+        Ok(Token::new(TokenKind::String, Span::new(start, self.pos), value))
+    }
+
+    /// Lexes `r"..."` or `r#"..."#`, where the content is taken verbatim and the
+    /// string only ends at a quote followed by as many `#` as the opening one.
+    fn lex_raw_string(&mut self, start: usize) -> super::Result<Token> {
+        self.next();
+        let hashes = self.take_while(|c| c == '#', false).len();
+        if self.next() != Some('"') {
+            return Err(LexerError::unexpected_token(
+                self.string[start..self.pos].to_string(),
+                Span::new(start, self.pos),
+            ));
+        }
+        let opening = Span::new(start, self.pos);
+        let terminator = format!("\"{}", "#".repeat(hashes));
+
+        match self.string[self.pos..].find(&terminator) {
+            Some(len) => {
+                let value = self.take(len, false);
+                self.take(terminator.len(), false);
+                Ok(Token::new(TokenKind::String, Span::new(start, self.pos), value))
+            }
+            None => Err(LexerError::unterminated_string(opening)),
+        }
+    }
+
     fn process_token(&mut self, c: char, start: usize, peek: bool) -> super::Result<Token> {
         let kind = match c {
+            'r' if matches!(self.string[self.pos + 1..].chars().next(), Some('"' | '#')) => {
+                let (pos, remaining) = (self.pos, self.remaining);
+                let token = self.lex_raw_string(start);
+                if peek {
+                    self.pos = pos;
+                    self.remaining = remaining;
+                }
+                token?
+            }
             'a'..='z' | 'A'..='Z' | '_' => {
                 let value = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_', peek);
                 let kind = match value.as_str() {
@@ -99,10 +289,14 @@ impl Cursor {
                     "else" => TokenKind::KeywordElse,
                     "func" => TokenKind::KeywordFunc,
                     "return" => TokenKind::KeywordReturn,
+                    "as" => TokenKind::KeywordAs,
+                    "import" => TokenKind::KeywordImport,
+                    "pub" => TokenKind::KeywordPub,
                     "int" => TokenKind::TypeInt,
                     "float" => TokenKind::TypeFloat,
                     "bool" => TokenKind::TypeBool,
                     "string" => TokenKind::TypeString,
+                    "char" => TokenKind::TypeChar,
                     "void" => TokenKind::TypeVoid,
                     _ => TokenKind::Identifier,
                 };
@@ -110,33 +304,40 @@ impl Cursor {
                 Token::new(kind, Span::new(start, self.pos), value)
             }
             '0'..='9' => {
-                let value = self.take_while(char::is_numeric, peek);
-                Token::new(TokenKind::Number, Span::new(start, self.pos), value)
-            }
-            '"' => {
+                let (pos, remaining) = (self.pos, self.remaining);
+                let token = self.lex_number(start);
                 if peek {
-                    self.pos += 1;
-                    self.remaining -= 1;
-                } else {
-                    self.take(1, peek);
+                    self.pos = pos;
+                    self.remaining = remaining;
                 }
-                let value = self.take_while(|c| c != '"', peek);
+                token?
+            }
+            '"' => {
+                let (pos, remaining) = (self.pos, self.remaining);
+                let token = self.lex_string(start);
                 if peek {
-                    self.pos -= 1;
-                    self.remaining += 1;
-                } else {
-                    self.take(1, peek);
+                    self.pos = pos;
+                    self.remaining = remaining;
                 }
-                Token::new(TokenKind::String, Span::new(start, self.pos), value.to_string())
+                token?
             }
             '\'' => {
-                let value = self.take(1, peek);
-                Token::new(TokenKind::Char, Span::new(start, self.pos), value.to_string())
+                let (pos, remaining) = (self.pos, self.remaining);
+                let token = self.lex_char(start);
+                if peek {
+                    self.pos = pos;
+                    self.remaining = remaining;
+                }
+                token?
             }
             ',' => {
                 self.take(1, peek);
                 Token::new(TokenKind::Comma, Span::new(start, self.pos), ",".to_string())
             }
+            '.' => {
+                self.take(1, peek);
+                Token::new(TokenKind::Dot, Span::new(start, self.pos), ".".to_string())
+            }
             '(' => {
                 self.take(1, peek);
                 Token::new(TokenKind::ParenL, Span::new(start, self.pos), "(".to_string())
@@ -186,6 +387,23 @@ impl Cursor {
                 self.take(1, peek);
                 Token::new(TokenKind::Multiply, Span::new(start, self.pos), "*".to_string())
             }
+            '/' if self.string[self.pos + 1..].starts_with('/') => {
+                let text = self.take_while(|c| c != '\n', peek);
+                if self.lossless {
+                    return Ok(Token::new(TokenKind::Comment, Span::new(start, self.pos), text));
+                }
+                let text = text.trim_end();
+                // Peeking lexes the same comment again.
+                if self.comments.last().is_none_or(|comment| comment.span.lo < start) {
+                    let span = Span::new(start, start + text.len());
+                    self.comments.push(Token::new(TokenKind::Comment, span, text.to_string()));
+                }
+                if peek {
+                    self.peek_token()?
+                } else {
+                    self.next_token()?
+                }
+            }
             '/' => {
                 self.take(1, peek);
                 Token::new(TokenKind::Divide, Span::new(start, self.pos), "/".to_string())
@@ -198,11 +416,22 @@ impl Cursor {
                 self.take(1, peek);
                 Token::new(TokenKind::Colon, Span::new(start, self.pos), ":".to_string())
             }
+            '\n' if self.lossless => {
+                self.take(1, peek);
+                Token::new(TokenKind::Newline, Span::new(start, self.pos), "\n".to_string())
+            }
+            ' ' | '\t' | '\r' if self.lossless => {
+                let text = self.take_while(|c| c.is_whitespace() && c != '\n', peek);
+                Token::new(TokenKind::Whitespace, Span::new(start, self.pos), text)
+            }
             ' ' | '\t' | '\r' | '\n' => {
                 
                 let v = self.take_while(|c| c.is_whitespace(), false);
-                let c = self.peek_token()?;
-                c
+                if peek {
+                    self.peek_token()?
+                } else {
+                    self.next_token()?
+                }
             }
             _ => return Err(crate::error::LexerError::unexpected_token(
                 format!("{}", c),
@@ -224,23 +453,34 @@ impl Cursor {
             });
         };
 
-        self.process_token(c, start, false)
-        
+        let mut token = self.process_token(c, start, false)?;
+        if self.lossless {
+            token.value = self.string[token.span.lo..token.span.hi].to_string();
+        }
+        Ok(token)
     }
 
+    /// Lexes the next token without consuming it, only the whitespace before it. The
+    /// token is lexed as by `next_token`, so that its span covers its whole text.
     pub fn peek_token(&mut self) -> super::Result<Token> {
-        let c = if let Some(c) = self.peek() {
-            c
-        } else {
-            return Ok(Token {
-                kind: TokenKind::Eof,
-                span: Span::new(self.pos, self.pos),
-                value: String::new(),
-            });
-        };
-        
-        let start = self.pos;
-        self.process_token(c, start, true)
+        if !self.lossless {
+            self.take_while(|c| c.is_whitespace(), false);
+        }
+        let (pos, remaining) = (self.pos, self.remaining);
+        let token = self.next_token();
+        self.pos = pos;
+        self.remaining = remaining;
+        token
+    }
+
+    /// Lexes the token after the next one without consuming either.
+    pub fn peek_second_token(&mut self) -> super::Result<Token> {
+        self.peek_token()?;
+        let (pos, remaining) = (self.pos, self.remaining);
+        let token = self.next_token().and_then(|_| self.peek_token());
+        self.pos = pos;
+        self.remaining = remaining;
+        token
     }
 
 }
@@ -12,9 +12,69 @@ pub struct UnexpectedToken {
     pub span: Span,
 }
 
+#[derive(Diagnostics, Debug)]
+#[code = 2]
+#[message = "Unterminated character literal"]
+#[label = "character literal starts here"]
+#[note = "Add a closing `'` on the same line"]
+pub struct UnterminatedChar {
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[code = 3]
+#[message = "Invalid character literal `{literal}`"]
+#[label = "{reason}"]
+#[note = "A character literal holds exactly one character, use a string for more"]
+pub struct InvalidChar {
+    literal: String,
+    reason: String,
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[code = 4]
+#[message = "Invalid escape sequence `{escape}`"]
+#[label = "invalid escape sequence"]
+#[note = "Valid escapes are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\`, `\\'`, `\\\"`, `\\x00` to `\\x7F` and `\\u{{0}}` to `\\u{{10FFFF}}`"]
+pub struct InvalidEscape {
+    escape: String,
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[code = 5]
+#[message = "Unterminated string literal"]
+#[label = "string literal starts here"]
+#[note = "Add a closing `\"`"]
+pub struct UnterminatedString {
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[code = 6]
+#[message = "Invalid integer literal `{literal}`"]
+#[label = "{reason}"]
+#[note = "Integer literals are decimal, or use the `0x`, `0o` or `0b` prefix, digits may be separated by `_`"]
+pub struct InvalidIntLiteral {
+    literal: String,
+    reason: String,
+    #[span]
+    pub span: Span,
+}
+
 #[derive(Debug)]
 pub enum LexerError {
     UnexpectedToken(UnexpectedToken),
+    UnterminatedChar(UnterminatedChar),
+    InvalidChar(InvalidChar),
+    InvalidEscape(InvalidEscape),
+    UnterminatedString(UnterminatedString),
+    InvalidIntLiteral(InvalidIntLiteral),
 }
 
 impl LexerError {
@@ -24,36 +84,89 @@ impl LexerError {
             span,
         })
     }
+
+    pub fn unterminated_char(span: Span) -> Self {
+        LexerError::UnterminatedChar(UnterminatedChar { span })
+    }
+
+    pub fn invalid_char(literal: String, reason: String, span: Span) -> Self {
+        LexerError::InvalidChar(InvalidChar {
+            literal,
+            reason,
+            span,
+        })
+    }
+
+    pub fn invalid_escape(escape: String, span: Span) -> Self {
+        LexerError::InvalidEscape(InvalidEscape { escape, span })
+    }
+
+    pub fn unterminated_string(span: Span) -> Self {
+        LexerError::UnterminatedString(UnterminatedString { span })
+    }
+
+    pub fn invalid_int_literal(literal: String, reason: String, span: Span) -> Self {
+        LexerError::InvalidIntLiteral(InvalidIntLiteral {
+            literal,
+            reason,
+            span,
+        })
+    }
 }
 
 impl Diagnostics for LexerError {
     fn message(&self) -> String {
         match self {
             LexerError::UnexpectedToken(err) => err.message(),
+            LexerError::UnterminatedChar(err) => err.message(),
+            LexerError::InvalidChar(err) => err.message(),
+            LexerError::InvalidEscape(err) => err.message(),
+            LexerError::UnterminatedString(err) => err.message(),
+            LexerError::InvalidIntLiteral(err) => err.message(),
         }
     }
 
     fn code(&self) -> u32 {
         match self {
             LexerError::UnexpectedToken(err) => err.code(),
+            LexerError::UnterminatedChar(err) => err.code(),
+            LexerError::InvalidChar(err) => err.code(),
+            LexerError::InvalidEscape(err) => err.code(),
+            LexerError::UnterminatedString(err) => err.code(),
+            LexerError::InvalidIntLiteral(err) => err.code(),
         }
     }
 
     fn label(&self) -> String {
         match self {
             LexerError::UnexpectedToken(err) => err.label(),
+            LexerError::UnterminatedChar(err) => err.label(),
+            LexerError::InvalidChar(err) => err.label(),
+            LexerError::InvalidEscape(err) => err.label(),
+            LexerError::UnterminatedString(err) => err.label(),
+            LexerError::InvalidIntLiteral(err) => err.label(),
         }
     }
 
     fn span(&self) -> Span {
         match self {
             LexerError::UnexpectedToken(err) => err.span(),
+            LexerError::UnterminatedChar(err) => err.span(),
+            LexerError::InvalidChar(err) => err.span(),
+            LexerError::InvalidEscape(err) => err.span(),
+            LexerError::UnterminatedString(err) => err.span(),
+            LexerError::InvalidIntLiteral(err) => err.span(),
         }
     }
 
     fn note(&self) -> Option<String> {
         match self {
             LexerError::UnexpectedToken(err) => err.note(),
+            LexerError::UnterminatedChar(err) => err.note(),
+            LexerError::InvalidChar(err) => err.note(),
+            LexerError::InvalidEscape(err) => err.note(),
+            LexerError::UnterminatedString(err) => err.note(),
+            LexerError::InvalidIntLiteral(err) => err.note(),
         }
     }
 }
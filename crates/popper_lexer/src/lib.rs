@@ -1,8 +1,10 @@
 use popper_ast::ast::Span;
+use popper_ast::cst::CstToken;
 use popper_ast::token::{Token, TokenKind};
 
 pub mod cursor;
 pub mod error;
+#[cfg(test)]
 mod tests;
 
 type Result<T> = std::result::Result<T, error::LexerError>;
@@ -22,3 +24,32 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>> {
     }
     Ok(tokens)
 }
+
+/// Lexes `input` in lossless mode, attaching the whitespace, newlines and comments to
+/// the tokens around them. The last token is `Eof`, its leading trivia holding the end
+/// of the input.
+pub fn tokenize_lossless(input: &str) -> Result<Vec<CstToken>> {
+    let mut cursor = cursor::Cursor::lossless(input);
+    let mut tokens: Vec<CstToken> = Vec::new();
+    let mut leading = Vec::new();
+    loop {
+        let token = cursor.next_token()?;
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::Newline | TokenKind::Comment => match tokens.last_mut() {
+                Some(last) if !last.ends_line() => last.trailing.push(token),
+                _ => leading.push(token),
+            },
+            _ => {
+                let eof = token.kind == TokenKind::Eof;
+                tokens.push(CstToken {
+                    leading: std::mem::take(&mut leading),
+                    token,
+                    trailing: Vec::new(),
+                });
+                if eof {
+                    return Ok(tokens);
+                }
+            }
+        }
+    }
+}
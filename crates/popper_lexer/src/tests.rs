@@ -1,3 +1,4 @@
+use crate::error::LexerError;
 use crate::tokenize;
 use popper_ast::token::{Token, TokenKind};
 
@@ -46,3 +47,108 @@ fn test_keyword() {
     assert_eq!(tokens[0].value, "let");
     assert_eq!(tokens[1].kind, TokenKind::Eof);
 }
+
+#[test]
+fn test_char() {
+    let input = "'a' '\\n' '\\u{1F600}' '\\''";
+    let tokens = tokenize(input).unwrap();
+    assert_eq!(tokens.len(), 5);
+    assert!(tokens[..4].iter().all(|token| token.kind == TokenKind::Char));
+    assert_eq!(tokens[0].value, "a");
+    assert_eq!(tokens[1].value, "\n");
+    assert_eq!(tokens[2].value, "\u{1F600}");
+    assert_eq!(tokens[3].value, "'");
+}
+
+#[test]
+fn test_invalid_char() {
+    assert!(matches!(tokenize("'a"), Err(LexerError::UnterminatedChar(_))));
+    assert!(matches!(tokenize("''"), Err(LexerError::InvalidChar(_))));
+    assert!(matches!(tokenize("'ab'"), Err(LexerError::InvalidChar(_))));
+    assert!(matches!(tokenize("'\\q'"), Err(LexerError::InvalidEscape(_))));
+    assert!(matches!(tokenize("'\\u{110000}'"), Err(LexerError::InvalidEscape(_))));
+}
+
+#[test]
+fn test_string_escapes() {
+    let input = r##""a\tb\n\\\"\0\x41\u{e9}" r"C:\path" r#"say "hi""#"##;
+    let tokens = tokenize(input).unwrap();
+    assert_eq!(tokens.len(), 4);
+    assert_eq!(tokens[0].value, "a\tb\n\\\"\0A\u{e9}");
+    assert_eq!(tokens[1].value, "C:\\path");
+    assert_eq!(tokens[2].kind, TokenKind::String);
+    assert_eq!(tokens[2].value, "say \"hi\"");
+}
+
+#[test]
+fn test_unterminated_string() {
+    match tokenize("let s = \"abc;\nlet t = 1;") {
+        Err(LexerError::UnterminatedString(err)) => assert_eq!((err.span.lo, err.span.hi), (8, 9)),
+        other => panic!("expected an unterminated string, got {other:?}"),
+    }
+    assert!(matches!(tokenize("r#\"abc\""), Err(LexerError::UnterminatedString(_))));
+    assert!(matches!(tokenize("\"\\x80\""), Err(LexerError::InvalidEscape(_))));
+}
+
+#[test]
+fn test_radix_number() {
+    let tokens = tokenize("0xFF 0o755 0b1010 1_000_000").unwrap();
+    assert_eq!(tokens.len(), 5);
+    assert!(tokens[..4].iter().all(|token| token.kind == TokenKind::Number));
+    assert_eq!(tokens[0].value, "0xFF");
+    assert_eq!(tokens[3].value, "1_000_000");
+}
+
+#[test]
+fn test_invalid_number() {
+    match tokenize("0b102") {
+        Err(LexerError::InvalidIntLiteral(err)) => assert_eq!((err.span.lo, err.span.hi), (4, 5)),
+        other => panic!("expected an invalid integer literal, got {other:?}"),
+    }
+    assert!(matches!(tokenize("0o8"), Err(LexerError::InvalidIntLiteral(_))));
+    assert!(matches!(tokenize("0x_"), Err(LexerError::InvalidIntLiteral(_))));
+}
+
+#[test]
+fn test_peek_token_span() {
+    let mut cursor = crate::cursor::Cursor::new("  foo ;");
+    let peeked = cursor.peek_token().unwrap();
+    assert_eq!(peeked.span, popper_ast::ast::Span::new(2, 5));
+    assert_eq!(cursor.next_token().unwrap(), peeked);
+    assert_eq!(cursor.peek_token().unwrap().span, popper_ast::ast::Span::new(6, 7));
+}
+
+#[test]
+fn test_comment() {
+    let mut cursor = crate::cursor::Cursor::new("a // b / c  \n/ \"//\"");
+    let kinds: Vec<TokenKind> = std::iter::from_fn(|| {
+        let token = cursor.next_token().unwrap();
+        (token.kind != TokenKind::Eof).then_some(token.kind)
+    })
+    .collect();
+    assert_eq!(kinds, [TokenKind::Identifier, TokenKind::Divide, TokenKind::String]);
+    assert_eq!(cursor.comments().len(), 1);
+    assert_eq!(cursor.comments()[0].value, "// b / c");
+    assert_eq!(cursor.comments()[0].span, popper_ast::ast::Span::new(2, 10));
+}
+
+#[test]
+fn test_lossless_trivia() {
+    let input = "// head\nlet s = \"a\\n\";  // tail\r\n\n\tf(0xFF);\n";
+    let tokens = crate::tokenize_lossless(input).unwrap();
+    let mut text = String::new();
+    tokens.iter().for_each(|token| token.write(&mut text));
+    assert_eq!(text, input);
+
+    let kinds = |trivia: &[Token]| trivia.iter().map(|token| token.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(kinds(&tokens[0].leading), [TokenKind::Comment, TokenKind::Newline]);
+    assert_eq!(tokens[3].token.value, "\"a\\n\"");
+    assert_eq!(tokens[4].token.kind, TokenKind::Semicolon);
+    assert_eq!(
+        kinds(&tokens[4].trailing),
+        [TokenKind::Whitespace, TokenKind::Comment, TokenKind::Newline]
+    );
+    assert_eq!(tokens[4].trailing[1].value, "// tail\r");
+    assert_eq!(kinds(&tokens[5].leading), [TokenKind::Newline, TokenKind::Whitespace]);
+    assert_eq!(tokens.last().unwrap().token.kind, TokenKind::Eof);
+}
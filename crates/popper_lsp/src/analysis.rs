@@ -0,0 +1,188 @@
+use crate::{completion, position, semantic_tokens, symbols, uri};
+use popper_ast::ast::{Expr, LangAst, LangNodeId, LangNodeKind, Span};
+use popper_ast::file::{File, FileId, SourceFileInfo};
+use popper_ast::layer::Ast;
+use popper_context::Context;
+use popper_driver::{check_with_scope_query, load_modules_with};
+use popper_error_core::{registry, ErrorTable, Severity};
+use popper_semantic_analyzer::SemanticAnalyzer;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The result of checking a document along with the modules it imports.
+pub struct Analysis {
+    context: Context,
+    errors: ErrorTable,
+    /// The file of the document, `None` if it could not be loaded.
+    file: Option<FileId>,
+    /// The analyzer of the document, `None` if the analysis did not reach it.
+    analyzer: Option<SemanticAnalyzer>,
+}
+
+impl Analysis {
+    /// Checks the document at `path`. Open documents are read from `sources`, the
+    /// other modules from the disk.
+    pub fn new(path: &Path, sources: &HashMap<PathBuf, String>) -> Analysis {
+        Analysis::check(path, sources, None)
+    }
+
+    /// Like `new`, also recording the symbols in scope at the byte offset `offset` of
+    /// the document for `completions`.
+    pub fn with_scope_query(path: &Path, sources: &HashMap<PathBuf, String>, offset: usize) -> Analysis {
+        Analysis::check(path, sources, Some(offset))
+    }
+
+    fn check(path: &Path, sources: &HashMap<PathBuf, String>, query: Option<usize>) -> Analysis {
+        let mut context = Context::new();
+        let mut errors = ErrorTable::new();
+        let path_str = path.to_string_lossy();
+
+        let modules = load_modules_with(&mut context, &path_str, sources, &mut errors).unwrap_or_default();
+        let file = context
+            .file_table()
+            .iter()
+            .find(|file| file.info().path() == path_str)
+            .map(File::id);
+        let mut analyzed = Vec::new();
+        if !errors.has_errors() {
+            let query = file.zip(query);
+            analyzed = check_with_scope_query(&context, &modules, &mut errors, query);
+        }
+        let analyzer = analyzed
+            .into_iter()
+            .find(|(id, _)| Some(*id) == file)
+            .map(|(_, analyzer)| analyzer);
+
+        Analysis {
+            context,
+            errors,
+            file,
+            analyzer,
+        }
+    }
+
+    fn info(&self, file: FileId) -> Option<&SourceFileInfo> {
+        self.context.get_file(file).map(File::info)
+    }
+
+    /// The LSP diagnostics reported in the document.
+    pub fn diagnostics(&self) -> Vec<Value> {
+        let Some(info) = self.file.and_then(|file| self.info(file)) else {
+            return Vec::new();
+        };
+
+        self.errors
+            .iter()
+            .filter(|error| Some(error.file()) == self.file)
+            .map(|error| {
+                let diagnostics = error.diagnostics();
+                let related: Vec<Value> = diagnostics
+                    .secondary_labels()
+                    .into_iter()
+                    .filter_map(|label| {
+                        let info = self.info(label.file.unwrap_or(error.file()))?;
+                        Some(json!({
+                            "location": {
+                                "uri": uri::from_path(Path::new(info.path())),
+                                "range": position::range(info.source(), label.span),
+                            },
+                            "message": label.message,
+                        }))
+                    })
+                    .collect();
+                json!({
+                    "range": position::range(info.source(), diagnostics.span()),
+                    "severity": match error.severity() {
+                        Severity::Error => 1,
+                        Severity::Warning => 2,
+                    },
+                    "code": registry::code_name(diagnostics.code()),
+                    "source": "popper",
+                    "message": diagnostics.message(),
+                    "relatedInformation": related,
+                })
+            })
+            .collect()
+    }
+
+    /// The smallest node of the document around `offset` for which `keep` holds.
+    fn node_at(&self, offset: usize, keep: impl Fn(LangNodeId) -> bool) -> Option<LangNodeId> {
+        let ast = self.analyzer.as_ref()?.ast();
+        ast.nodes()
+            .filter(|&id| {
+                let span = ast.get(id).span;
+                span.lo <= offset && offset <= span.hi
+            })
+            .filter(|&id| keep(id))
+            .min_by_key(|&id| {
+                let span = ast.get(id).span;
+                span.hi - span.lo
+            })
+    }
+
+    /// The type of the innermost typed node around `offset`, as markdown, with the span
+    /// of the node.
+    pub fn hover(&self, offset: usize) -> Option<(String, Span)> {
+        let analyzer = self.analyzer.as_ref()?;
+        let (ast, hir) = (analyzer.ast(), analyzer.hir());
+        let id = self.node_at(offset, |id| hir.get_type(id.into()).is_some())?;
+        let node = ast.get(id);
+        let ty = hir.get_type(id.into())?;
+
+        let text = match &node.kind {
+            LangNodeKind::Expr(Expr::Ident(name)) => format!("{}: {ty}", ast.get_symbol(name.0).name),
+            LangNodeKind::Let(let_) => format!("let {}: {ty}", ast.get_symbol(let_.name.0).name),
+            LangNodeKind::FunctionDef { name, .. } => {
+                format!("func {}: {ty}", ast.get_symbol(name.0).name)
+            }
+            _ => ty.to_string(),
+        };
+        Some((format!("```popper\n{text}\n```"), node.span))
+    }
+
+    /// The definition of the symbol used around `offset`, as the file it is in and its
+    /// span there.
+    pub fn definition(&self, offset: usize) -> Option<(&SourceFileInfo, Span)> {
+        let hir = self.analyzer.as_ref()?.hir();
+        let id = self.node_at(offset, |id| hir.get_definition(id.into()).is_some())?;
+        let definition = hir.get_definition(id.into())?;
+        let info = self.info(definition.file.or(self.file)?)?;
+        Some((info, definition.span))
+    }
+
+    /// The classified tokens of the document, as LSP semantic token data.
+    pub fn semantic_tokens(&self) -> Option<Value> {
+        let source = self.source()?;
+        let hir = self.analyzer.as_ref().map(SemanticAnalyzer::hir);
+        let tokens = semantic_tokens::classify(source, self.ast(), hir);
+        Some(semantic_tokens::encode(source, &tokens))
+    }
+
+    /// The outline of the document, empty if it could not be parsed.
+    pub fn document_symbols(&self) -> Option<Vec<Value>> {
+        let source = self.source()?;
+        Some(self.ast().map_or_else(Vec::new, |ast| symbols::document_symbols(source, ast)))
+    }
+
+    /// The completions at `offset`, which must be the offset given to `with_scope_query`.
+    pub fn completions(&self, offset: usize) -> Option<Vec<Value>> {
+        let source = self.source()?;
+        Some(completion::completions(source, offset, self.ast(), self.analyzer.as_ref()))
+    }
+
+    /// Whether the document could be parsed.
+    pub fn is_parsed(&self) -> bool {
+        self.ast().is_some()
+    }
+
+    /// The AST of the document, `None` if it could not be parsed.
+    fn ast(&self) -> Option<&LangAst> {
+        self.context.get_file(self.file?)?.ast()
+    }
+
+    /// The source of the document as it was analyzed.
+    pub fn source(&self) -> Option<&str> {
+        self.info(self.file?).map(SourceFileInfo::source)
+    }
+}
@@ -0,0 +1,107 @@
+use popper_ast::ast::{ArgumentParamDef, LangAst, LangNodeId, LangNodeKind, Visibility};
+use popper_ast::visit::{self, Visitor};
+use popper_semantic_analyzer::symbol_resolver::SymbolKind;
+use popper_semantic_analyzer::SemanticAnalyzer;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+/// `CompletionItemKind` values in the LSP specification.
+const FUNCTION: u32 = 3;
+const VARIABLE: u32 = 6;
+const KEYWORD: u32 = 14;
+
+/// The completions at `offset`: the symbols in scope there, innermost first, with their
+/// type as detail, then the keywords starting a statement if the cursor is at the start
+/// of one. `analyzer` must have been built with a scope query at `offset`.
+pub fn completions(source: &str, offset: usize, ast: Option<&LangAst>, analyzer: Option<&SemanticAnalyzer>) -> Vec<Value> {
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+    if let Some((analyzer, symbols)) = analyzer.and_then(|analyzer| Some((analyzer, analyzer.scope_at_query()?))) {
+        for symbol in symbols {
+            if symbol.file.is_some() && symbol.visibility == Visibility::Private {
+                continue;
+            }
+            let name = &analyzer.ast().get_symbol(symbol.id).name;
+            // An inner symbol shadows the outer ones of the same name.
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            let kind = match symbol.kind {
+                SymbolKind::Function | SymbolKind::ExternFunction => FUNCTION,
+                SymbolKind::Variable | SymbolKind::Parameter => VARIABLE,
+            };
+            items.push(json!({ "label": name, "kind": kind, "detail": symbol.ty.to_string() }));
+        }
+    }
+
+    if at_statement_start(source, offset) {
+        let (in_block, in_function) = ast.map_or((false, false), |ast| enclosing(ast, offset));
+        let mut keywords = vec!["let", "if", "func"];
+        if in_function {
+            keywords.push("return");
+        }
+        if !in_block {
+            keywords.extend(["import", "pub"]);
+        }
+        items.extend(keywords.into_iter().map(|keyword| json!({ "label": keyword, "kind": KEYWORD })));
+    }
+    items
+}
+
+/// Whether only the name being typed separates `offset` from the start of a statement.
+fn at_statement_start(source: &str, offset: usize) -> bool {
+    let Some(before) = source.get(..offset) else {
+        return false;
+    };
+    let before = before
+        .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+        .trim_end();
+    before.is_empty() || before.ends_with([';', '{', '}'])
+}
+
+/// Whether `offset` is in a block other than the root one, and in the body of a function.
+fn enclosing(ast: &LangAst, offset: usize) -> (bool, bool) {
+    let mut visitor = Enclosing {
+        offset,
+        in_block: false,
+        in_function: false,
+    };
+    visitor.visit_node(ast, ast.root);
+    (visitor.in_block, visitor.in_function)
+}
+
+struct Enclosing {
+    offset: usize,
+    in_block: bool,
+    in_function: bool,
+}
+
+impl Enclosing {
+    fn contains(&self, ast: &LangAst, id: LangNodeId) -> bool {
+        let span = ast.get(id).span;
+        span.lo < self.offset && self.offset < span.hi
+    }
+}
+
+impl<'ast> Visitor<'ast> for Enclosing {
+    fn visit_block(&mut self, ast: &'ast LangAst, id: LangNodeId, items: &'ast [LangNodeId]) {
+        if id != ast.root && self.contains(ast, id) {
+            self.in_block = true;
+        }
+        visit::walk_block(self, ast, id, items)
+    }
+
+    fn visit_function_def(
+        &mut self,
+        ast: &'ast LangAst,
+        id: LangNodeId,
+        params: &'ast [ArgumentParamDef],
+        body: Option<LangNodeId>,
+    ) {
+        let is_expr = matches!(ast.get(id).kind, LangNodeKind::FunctionDef { is_expr: true, .. });
+        if body.is_some_and(|body| !is_expr && self.contains(ast, body)) {
+            self.in_function = true;
+        }
+        visit::walk_function_def(self, ast, id, params, body)
+    }
+}
@@ -0,0 +1,8 @@
+pub mod analysis;
+pub mod completion;
+pub mod position;
+pub mod semantic_tokens;
+pub mod server;
+pub mod symbols;
+pub mod transport;
+pub mod uri;
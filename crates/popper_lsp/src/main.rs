@@ -0,0 +1,11 @@
+fn main() {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    match popper_lsp::server::run(stdin.lock(), stdout.lock()) {
+        Ok(code) => std::process::exit(code),
+        Err(err) => {
+            eprintln!("popper-lsp: {err}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Conversions between byte offsets and LSP positions, which count lines from 0 and
+//! columns in UTF-16 code units.
+
+use popper_ast::ast::Span;
+use serde_json::{json, Value};
+
+pub fn position(source: &str, offset: usize) -> Value {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let character: usize = before[line_start..].chars().map(char::len_utf16).sum();
+    json!({ "line": line, "character": character })
+}
+
+pub fn range(source: &str, span: Span) -> Value {
+    json!({ "start": position(source, span.lo), "end": position(source, span.hi) })
+}
+
+/// The byte offset of an LSP position, clamped to the end of its line.
+pub fn offset(source: &str, position: &Value) -> Option<usize> {
+    let line = position["line"].as_u64()? as usize;
+    let character = position["character"].as_u64()? as usize;
+
+    let line_start = if line == 0 {
+        0
+    } else {
+        source.match_indices('\n').nth(line - 1)?.0 + 1
+    };
+    let mut units = 0;
+    for (i, c) in source[line_start..].char_indices() {
+        if units >= character || c == '\n' {
+            return Some(line_start + i);
+        }
+        units += c.len_utf16();
+    }
+    Some(source.len())
+}
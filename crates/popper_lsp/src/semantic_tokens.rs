@@ -0,0 +1,228 @@
+use popper_ast::ast::{ArgumentParamDef, Expr, Ident, LangAst, LangNodeId, LangNodeKind, Let, Span};
+use popper_ast::attribute::Attribute;
+use popper_ast::visit::{self, Visitor};
+use popper_ast::token::TokenKind;
+use popper_lexer::cursor::Cursor;
+use popper_semantic_analyzer::hir::Hir;
+use popper_semantic_analyzer::symbol_resolver::SymbolKind;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The token types of the legend, indexed by `TokenClass::token_type`.
+pub const TOKEN_TYPES: &[&str] = &[
+    "namespace", "keyword", "type", "function", "parameter", "variable", "string", "number",
+    "operator",
+];
+
+/// The token modifiers of the legend, as bits of `TokenClass::token_type`. `extern` is
+/// not a standard modifier and marks the functions declared with `func(C)`.
+pub const TOKEN_MODIFIERS: &[&str] = &["declaration", "extern"];
+
+const DECLARATION: u32 = 1 << 0;
+const EXTERN: u32 = 1 << 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenClass {
+    /// A segment of an import path.
+    Namespace,
+    Keyword,
+    Type,
+    Function,
+    ExternFunction,
+    Parameter,
+    Variable,
+    String,
+    Number,
+    Operator,
+}
+
+impl TokenClass {
+    /// The index of the token type in `TOKEN_TYPES` and the modifiers implied by the class.
+    fn token_type(self) -> (u32, u32) {
+        match self {
+            TokenClass::Namespace => (0, 0),
+            TokenClass::Keyword => (1, 0),
+            TokenClass::Type => (2, 0),
+            TokenClass::Function => (3, 0),
+            TokenClass::ExternFunction => (3, EXTERN),
+            TokenClass::Parameter => (4, 0),
+            TokenClass::Variable => (5, 0),
+            TokenClass::String => (6, 0),
+            TokenClass::Number => (7, 0),
+            TokenClass::Operator => (8, 0),
+        }
+    }
+
+    fn from_symbol_kind(kind: SymbolKind) -> TokenClass {
+        match kind {
+            SymbolKind::Variable => TokenClass::Variable,
+            SymbolKind::Parameter => TokenClass::Parameter,
+            SymbolKind::Function => TokenClass::Function,
+            SymbolKind::ExternFunction => TokenClass::ExternFunction,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub span: Span,
+    pub class: TokenClass,
+    /// Whether the token is the name of a definition rather than a use.
+    pub declaration: bool,
+}
+
+/// Collects the classes of the identifiers known from the AST and the resolved symbols,
+/// by span. Identifiers inside an import are collected apart, as the spans of the imports.
+struct IdentifierClasses<'a> {
+    hir: Option<&'a Hir>,
+    classes: HashMap<Span, (TokenClass, bool)>,
+    imports: Vec<Span>,
+}
+
+impl<'ast> Visitor<'ast> for IdentifierClasses<'_> {
+    fn visit_expr(&mut self, ast: &'ast LangAst, id: LangNodeId, expr: &'ast Expr) {
+        if let Expr::Ident(_) = expr {
+            if let Some(definition) = self.hir.and_then(|hir| hir.get_definition(id.into())) {
+                let class = TokenClass::from_symbol_kind(definition.kind);
+                self.classes.insert(ast.get(id).span, (class, false));
+            }
+        }
+        visit::walk_expr(self, ast, id, expr)
+    }
+
+    fn visit_let(&mut self, ast: &'ast LangAst, id: LangNodeId, let_: &'ast Let) {
+        self.classes.insert(let_.name_span, (TokenClass::Variable, true));
+        visit::walk_let(self, ast, id, let_)
+    }
+
+    fn visit_function_def(
+        &mut self,
+        ast: &'ast LangAst,
+        id: LangNodeId,
+        params: &'ast [ArgumentParamDef],
+        body: Option<LangNodeId>,
+    ) {
+        if let LangNodeKind::FunctionDef { name_span, attrs, .. } = &ast.get(id).kind {
+            let class = if attrs.contains(&Attribute::StdCallC) {
+                TokenClass::ExternFunction
+            } else {
+                TokenClass::Function
+            };
+            self.classes.insert(*name_span, (class, true));
+        }
+        visit::walk_function_def(self, ast, id, params, body)
+    }
+
+    fn visit_param(&mut self, _ast: &'ast LangAst, param: &'ast ArgumentParamDef) {
+        self.classes.insert(param.name_span, (TokenClass::Parameter, true));
+    }
+
+    fn visit_import(&mut self, ast: &'ast LangAst, id: LangNodeId, _path: &'ast [Ident]) {
+        self.imports.push(ast.get(id).span);
+    }
+}
+
+fn identifier_classes(ast: &LangAst, hir: Option<&Hir>) -> (HashMap<Span, (TokenClass, bool)>, Vec<Span>) {
+    let mut visitor = IdentifierClasses {
+        hir,
+        classes: HashMap::new(),
+        imports: Vec::new(),
+    };
+    visitor.visit_node(ast, ast.root);
+    (visitor.classes, visitor.imports)
+}
+
+/// Classifies the tokens of `source`. Keywords, types, literals and operators only need
+/// the lexer; identifiers are classified with `ast` and the symbols resolved in `hir`
+/// when given, and left out otherwise. Stops at the first lexer error.
+pub fn classify(source: &str, ast: Option<&LangAst>, hir: Option<&Hir>) -> Vec<SemanticToken> {
+    let (classes, imports) = ast.map(|ast| identifier_classes(ast, hir)).unwrap_or_default();
+    let module_attrs = ast.and_then(|ast| ast.module_attrs.as_ref()).map(|attrs| attrs.span);
+
+    let mut tokens = Vec::new();
+    let mut cursor = Cursor::new(source);
+    while let Ok(token) = cursor.next_token() {
+        let (class, declaration) = match token.kind {
+            TokenKind::Eof => break,
+            TokenKind::Identifier => {
+                if let Some(&class) = classes.get(&token.span) {
+                    class
+                } else if token.value == "module"
+                    && module_attrs.is_some_and(|span| span.lo <= token.span.lo && token.span.hi <= span.hi)
+                {
+                    // Only a keyword in the module attributes.
+                    (TokenClass::Keyword, false)
+                } else if imports
+                    .iter()
+                    .any(|import| import.lo <= token.span.lo && token.span.hi <= import.hi)
+                {
+                    (TokenClass::Namespace, false)
+                } else {
+                    continue;
+                }
+            }
+            TokenKind::KeywordLet
+            | TokenKind::KeywordIf
+            | TokenKind::KeywordElse
+            | TokenKind::KeywordFunc
+            | TokenKind::KeywordReturn
+            | TokenKind::KeywordAs
+            | TokenKind::KeywordImport
+            | TokenKind::KeywordPub => (TokenClass::Keyword, false),
+            TokenKind::TypeInt
+            | TokenKind::TypeFloat
+            | TokenKind::TypeBool
+            | TokenKind::TypeString
+            | TokenKind::TypeChar
+            | TokenKind::TypeVoid => (TokenClass::Type, false),
+            TokenKind::String | TokenKind::Char => (TokenClass::String, false),
+            TokenKind::Number => (TokenClass::Number, false),
+            TokenKind::Arrow
+            | TokenKind::Eq
+            | TokenKind::Plus
+            | TokenKind::Minus
+            | TokenKind::Multiply
+            | TokenKind::Divide => (TokenClass::Operator, false),
+            _ => continue,
+        };
+        tokens.push(SemanticToken {
+            span: token.span,
+            class,
+            declaration,
+        });
+    }
+    tokens
+}
+
+/// Encodes `tokens` as the `data` of an LSP `SemanticTokens`: five integers per token,
+/// with the line and column relative to the previous token and columns in UTF-16 code
+/// units. Tokens spanning several lines are split into one token per line.
+pub fn encode(source: &str, tokens: &[SemanticToken]) -> Value {
+    let mut data = Vec::new();
+    let (mut previous_line, mut previous_start) = (0, 0);
+    let mut line = 0;
+    let mut line_start = 0;
+    for token in tokens {
+        let (token_type, mut modifiers) = token.class.token_type();
+        if token.declaration {
+            modifiers |= DECLARATION;
+        }
+
+        let mut lo = token.span.lo;
+        while lo < token.span.hi {
+            line += source[line_start..lo].matches('\n').count();
+            line_start = source[..lo].rfind('\n').map_or(0, |i| i + 1);
+            let hi = source[lo..token.span.hi].find('\n').map_or(token.span.hi, |i| lo + i);
+            let start: usize = source[line_start..lo].chars().map(char::len_utf16).sum();
+            let length: usize = source[lo..hi].chars().map(char::len_utf16).sum();
+
+            if length > 0 {
+                let delta_start = if line == previous_line { start - previous_start } else { start };
+                data.extend([line - previous_line, delta_start, length, token_type as usize, modifiers as usize]);
+                (previous_line, previous_start) = (line, start);
+            }
+            lo = hi + 1;
+        }
+    }
+    json!(data)
+}
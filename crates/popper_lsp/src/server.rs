@@ -0,0 +1,208 @@
+use crate::analysis::Analysis;
+use crate::transport::{read_message, write_message};
+use crate::{position, semantic_tokens, uri};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// A language server over the documents opened by the client. Every change re-checks
+/// all the open documents, since they may import each other.
+#[derive(Default)]
+pub struct Server {
+    /// The text of the open documents, by path.
+    documents: HashMap<PathBuf, String>,
+    analyses: HashMap<PathBuf, Analysis>,
+    shutdown: bool,
+}
+
+fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: &Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn document_path(params: &Value) -> Option<PathBuf> {
+    uri::to_path(params["textDocument"]["uri"].as_str()?)
+}
+
+impl Server {
+    pub fn new() -> Server {
+        Server::default()
+    }
+
+    /// Handles a message of the client, returning the messages to send back.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let Some(id) = message.get("id") else {
+            return self.handle_notification(method, params);
+        };
+
+        let result = match method {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "completionProvider": {},
+                    "documentSymbolProvider": true,
+                    "semanticTokensProvider": {
+                        "legend": {
+                            "tokenTypes": semantic_tokens::TOKEN_TYPES,
+                            "tokenModifiers": semantic_tokens::TOKEN_MODIFIERS,
+                        },
+                        "full": true,
+                    },
+                },
+                "serverInfo": { "name": "popper-lsp", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => {
+                self.shutdown = true;
+                Some(Value::Null)
+            }
+            "textDocument/hover" => self.hover(params),
+            "textDocument/definition" => self.definition(params),
+            "textDocument/completion" => self.completion(params),
+            "textDocument/semanticTokens/full" => self
+                .analysis(params)
+                .and_then(Analysis::semantic_tokens)
+                .map(|data| json!({ "data": data })),
+            "textDocument/documentSymbol" => self
+                .analysis(params)
+                .and_then(Analysis::document_symbols)
+                .map(Value::from),
+            _ => return vec![error_response(id, METHOD_NOT_FOUND, &format!("unknown method `{method}`"))],
+        };
+        match result {
+            Some(result) => vec![response(id, result)],
+            None => vec![error_response(id, INVALID_PARAMS, "invalid parameters")],
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let Some(path) = document_path(params) else {
+            return Vec::new();
+        };
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(path, text.to_string());
+            }
+            "textDocument/didChange" => {
+                // The server asks for full synchronization, so the last change holds the
+                // whole text.
+                let changes = params["contentChanges"].as_array();
+                let Some(text) = changes.and_then(|changes| changes.last()?["text"].as_str()) else {
+                    return Vec::new();
+                };
+                self.documents.insert(path, text.to_string());
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&path);
+                self.analyses.remove(&path);
+                let params = json!({ "uri": uri::from_path(&path), "diagnostics": [] });
+                let mut messages = vec![notification("textDocument/publishDiagnostics", params)];
+                messages.extend(self.reanalyze());
+                return messages;
+            }
+            _ => return Vec::new(),
+        }
+        self.reanalyze()
+    }
+
+    /// Checks every open document, returning their diagnostics.
+    fn reanalyze(&mut self) -> Vec<Value> {
+        let mut paths: Vec<&PathBuf> = self.documents.keys().collect();
+        paths.sort();
+        self.analyses = paths
+            .into_iter()
+            .map(|path| (path.clone(), Analysis::new(path, &self.documents)))
+            .collect();
+
+        let mut paths: Vec<&PathBuf> = self.analyses.keys().collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let params = json!({
+                    "uri": uri::from_path(path),
+                    "diagnostics": self.analyses[path].diagnostics(),
+                });
+                notification("textDocument/publishDiagnostics", params)
+            })
+            .collect()
+    }
+
+    fn analysis(&self, params: &Value) -> Option<&Analysis> {
+        self.analyses.get(&document_path(params)?)
+    }
+
+    /// The analysis of the document of a position request, with the offset of the position.
+    fn analysis_at(&self, params: &Value) -> Option<(&Analysis, usize)> {
+        let analysis = self.analysis(params)?;
+        let offset = position::offset(analysis.source()?, &params["position"])?;
+        Some((analysis, offset))
+    }
+
+    fn hover(&self, params: &Value) -> Option<Value> {
+        let (analysis, offset) = self.analysis_at(params)?;
+        Some(match analysis.hover(offset) {
+            Some((contents, span)) => json!({
+                "contents": { "kind": "markdown", "value": contents },
+                "range": position::range(analysis.source()?, span),
+            }),
+            None => Value::Null,
+        })
+    }
+
+    fn definition(&self, params: &Value) -> Option<Value> {
+        let (analysis, offset) = self.analysis_at(params)?;
+        Some(match analysis.definition(offset) {
+            Some((info, span)) => json!({
+                "uri": uri::from_path(Path::new(info.path())),
+                "range": position::range(info.source(), span),
+            }),
+            None => Value::Null,
+        })
+    }
+
+    /// Re-checks the document up to the position of the request to list the symbols in
+    /// scope there.
+    fn completion(&self, params: &Value) -> Option<Value> {
+        let path = document_path(params)?;
+        let offset = position::offset(self.documents.get(&path)?, &params["position"])?;
+        let mut analysis = Analysis::with_scope_query(&path, &self.documents, offset);
+        if !analysis.is_parsed() {
+            // The statement being typed usually lacks its `;`, which leaves no AST.
+            let mut sources = self.documents.clone();
+            sources.get_mut(&path)?.insert(offset, ';');
+            analysis = Analysis::with_scope_query(&path, &sources, offset);
+        }
+        analysis.completions(offset).map(Value::from)
+    }
+}
+
+/// Serves the client speaking on `input` and `output` until it sends `exit`. Returns the
+/// exit code of the server: 0 if the client asked for a shutdown first, 1 otherwise.
+pub fn run(mut input: impl BufRead, mut output: impl Write) -> io::Result<i32> {
+    let mut server = Server::new();
+    while let Some(message) = read_message(&mut input)? {
+        if message["method"] == "exit" {
+            return Ok(if server.shutdown { 0 } else { 1 });
+        }
+        for reply in server.handle(&message) {
+            write_message(&mut output, &reply)?;
+        }
+    }
+    Ok(1)
+}
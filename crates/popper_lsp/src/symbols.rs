@@ -0,0 +1,40 @@
+use crate::position;
+use popper_ast::ast::{LangAst, LangNodeKind};
+use popper_ast::type_::Type;
+use serde_json::{json, Value};
+
+/// `SymbolKind.Function` in the LSP specification.
+const FUNCTION: u32 = 12;
+
+/// The outline of a document: its top-level functions, with their signature as detail.
+pub fn document_symbols(source: &str, ast: &LangAst) -> Vec<Value> {
+    let LangNodeKind::Block(items) = &ast.get(ast.root).kind else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|&id| {
+            let node = ast.get(id);
+            let LangNodeKind::FunctionDef {
+                name,
+                name_span,
+                params,
+                ret,
+                ..
+            } = &node.kind
+            else {
+                return None;
+            };
+            let param_types = params.iter().map(|param| param.ty.clone()).collect();
+            let signature = Type::Function(param_types, Box::new(ret.clone()));
+            Some(json!({
+                "name": ast.get_symbol(name.0).name,
+                "detail": signature.to_string(),
+                "kind": FUNCTION,
+                "range": position::range(source, node.span),
+                "selectionRange": position::range(source, *name_span),
+            }))
+        })
+        .collect()
+}
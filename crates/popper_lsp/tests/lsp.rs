@@ -0,0 +1,281 @@
+use popper_lsp::transport::{read_message, write_message};
+use serde_json::{json, Value};
+use std::io::BufReader;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// A client driving `popper-lsp` over its stdio.
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start() -> Client {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_popper-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Client {
+            child,
+            stdin,
+            stdout,
+            next_id: 0,
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut self.stdin, &message).unwrap();
+    }
+
+    /// Sends a request and returns its result, skipping the notifications sent before.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let message = json!({ "jsonrpc": "2.0", "id": self.next_id, "method": method, "params": params });
+        write_message(&mut self.stdin, &message).unwrap();
+        loop {
+            let message = read_message(&mut self.stdout).unwrap().unwrap();
+            if message["id"] == self.next_id {
+                return message["result"].clone();
+            }
+        }
+    }
+
+    /// Waits for the next notification of `method` and returns its parameters.
+    fn notification(&mut self, method: &str) -> Value {
+        loop {
+            let message = read_message(&mut self.stdout).unwrap().unwrap();
+            if message["method"] == method {
+                return message["params"].clone();
+            }
+        }
+    }
+}
+
+fn position(line: u64, character: u64) -> Value {
+    json!({ "line": line, "character": character })
+}
+
+#[test]
+fn test_diagnostics_hover_and_definition() {
+    let path = std::env::temp_dir().join("popper-lsp-test").join("main.pop");
+    let uri = format!("file://{}", path.display());
+    let mut client = Client::start();
+
+    let result = client.request("initialize", json!({ "capabilities": {} }));
+    assert_eq!(result["capabilities"]["hoverProvider"], true);
+    assert_eq!(result["capabilities"]["definitionProvider"], true);
+    client.notify("initialized", json!({}));
+
+    let text = "func main() -> int {\n    return y;\n}\n";
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": uri, "languageId": "popper", "version": 1, "text": text } }),
+    );
+    let params = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(params["uri"], uri.as_str());
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], "E1001");
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(diagnostics[0]["range"]["start"], position(1, 11));
+    assert_eq!(diagnostics[0]["range"]["end"], position(1, 12));
+
+    let text = "func add(a: int, b: int) -> int {\n    return a + b;\n}\n\nfunc main() -> int {\n    let x = 1;\n    return add(x, 2);\n}\n";
+    client.notify(
+        "textDocument/didChange",
+        json!({ "textDocument": { "uri": uri, "version": 2 }, "contentChanges": [{ "text": text }] }),
+    );
+    let params = client.notification("textDocument/publishDiagnostics");
+    assert_eq!(params["diagnostics"], json!([]));
+
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": position(6, 15) }),
+    );
+    assert_eq!(hover["contents"]["value"], "```popper\nx: int\n```");
+    assert_eq!(hover["range"]["start"], position(6, 15));
+
+    let definition = client.request(
+        "textDocument/definition",
+        json!({ "textDocument": { "uri": uri }, "position": position(6, 12) }),
+    );
+    assert_eq!(definition["uri"], uri.as_str());
+    assert_eq!(definition["range"]["start"], position(0, 0));
+
+    assert_eq!(client.request("shutdown", Value::Null), Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.child.wait().unwrap().success());
+}
+
+#[test]
+fn test_semantic_tokens_and_document_symbols() {
+    let path = std::env::temp_dir().join("popper-lsp-test").join("tokens.pop");
+    let uri = format!("file://{}", path.display());
+    let mut client = Client::start();
+
+    let result = client.request("initialize", json!({ "capabilities": {} }));
+    let legend = &result["capabilities"]["semanticTokensProvider"]["legend"];
+    assert_eq!(legend["tokenTypes"][3], "function");
+    client.notify("initialized", json!({}));
+
+    let text = "func(C) puts(s: string);\n\nfunc twice(n: int) -> int {\n    let m = n + n;\n    puts(\"x\");\n    return m;\n}\n";
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": uri, "languageId": "popper", "version": 1, "text": text } }),
+    );
+
+    let tokens = client.request("textDocument/semanticTokens/full", json!({ "textDocument": { "uri": uri } }));
+    let data: Vec<u64> = serde_json::from_value(tokens["data"].clone()).unwrap();
+    let tokens: Vec<&[u64]> = data.chunks(5).collect();
+    // (line delta, column delta, length, type, modifiers), with the types indexing the
+    // legend: 1 keyword, 2 type, 3 function, 4 parameter, 5 variable, 6 string, 8 operator.
+    let expected: &[[u64; 5]] = &[
+        [0, 0, 4, 1, 0],  // func
+        [0, 8, 4, 3, 3],  // puts, an extern declaration
+        [0, 5, 1, 4, 1],  // s
+        [0, 3, 6, 2, 0],  // string
+        [2, 0, 4, 1, 0],  // func
+        [0, 5, 5, 3, 1],  // twice
+        [0, 6, 1, 4, 1],  // n
+        [0, 3, 3, 2, 0],  // int
+        [0, 5, 2, 8, 0],  // ->
+        [0, 3, 3, 2, 0],  // int
+        [1, 4, 3, 1, 0],  // let
+        [0, 4, 1, 5, 1],  // m
+        [0, 2, 1, 8, 0],  // =
+        [0, 2, 1, 4, 0],  // n
+        [0, 2, 1, 8, 0],  // +
+        [0, 2, 1, 4, 0],  // n
+        [1, 4, 4, 3, 2],  // puts
+        [0, 5, 3, 6, 0],  // "x"
+        [1, 4, 6, 1, 0],  // return
+        [0, 7, 1, 5, 0],  // m
+    ];
+    assert_eq!(tokens, expected.iter().map(|token| &token[..]).collect::<Vec<_>>());
+
+    let symbols = client.request("textDocument/documentSymbol", json!({ "textDocument": { "uri": uri } }));
+    let symbols = symbols.as_array().unwrap();
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[0]["name"], "puts");
+    assert_eq!(symbols[0]["detail"], "fn(string) -> void");
+    assert_eq!(symbols[1]["name"], "twice");
+    assert_eq!(symbols[1]["detail"], "fn(int) -> int");
+    assert_eq!(symbols[1]["kind"], 12);
+    assert_eq!(symbols[1]["selectionRange"]["start"], position(2, 5));
+
+    // `module` is a keyword only in the module attributes.
+    let uri = format!("file://{}", path.with_file_name("module.pop").display());
+    let text = "module(allow(unused));\nlet module = 1;\n";
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": uri, "languageId": "popper", "version": 1, "text": text } }),
+    );
+    let tokens = client.request("textDocument/semanticTokens/full", json!({ "textDocument": { "uri": uri } }));
+    let data: Vec<u64> = serde_json::from_value(tokens["data"].clone()).unwrap();
+    let tokens: Vec<&[u64]> = data.chunks(5).collect();
+    let expected: &[[u64; 5]] = &[
+        [0, 0, 6, 1, 0], // module
+        [1, 0, 3, 1, 0], // let
+        [0, 4, 6, 5, 1], // module
+        [0, 7, 1, 8, 0], // =
+        [0, 2, 1, 7, 0], // 1
+    ];
+    assert_eq!(tokens, expected.iter().map(|token| &token[..]).collect::<Vec<_>>());
+
+    client.request("shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.child.wait().unwrap().success());
+}
+
+#[test]
+fn test_completion() {
+    let path = std::env::temp_dir().join("popper-lsp-test").join("completion.pop");
+    let uri = format!("file://{}", path.display());
+    let mut client = Client::start();
+
+    client.request("initialize", json!({ "capabilities": {} }));
+    client.notify("initialized", json!({}));
+
+    // The statement being typed on line 3 has no `;` yet.
+    let text = "let n = 1;\n\nfunc add(a: int, n: int) -> int {\n    let sum = a + n;\n    su\n    let later = 2;\n}\n";
+    client.notify(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": uri, "languageId": "popper", "version": 1, "text": text } }),
+    );
+
+    let items = client.request(
+        "textDocument/completion",
+        json!({ "textDocument": { "uri": uri }, "position": position(4, 6) }),
+    );
+    let labels: Vec<&str> = items.as_array().unwrap().iter().map(|item| item["label"].as_str().unwrap()).collect();
+    // The parameter `n` shadows the global one, `later` is not defined yet.
+    assert_eq!(labels, ["sum", "n", "a", "add", "let", "if", "func", "return"]);
+    let items = items.as_array().unwrap();
+    assert_eq!(items[0]["detail"], "int");
+    assert_eq!(items[0]["kind"], 6);
+    assert_eq!(items[3]["detail"], "fn(int, int) -> int");
+    assert_eq!(items[3]["kind"], 3);
+    assert_eq!(items[4]["kind"], 14);
+
+    client.request("shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.child.wait().unwrap().success());
+}
+
+#[test]
+fn test_completion_after_error() {
+    let dir = std::env::temp_dir().join("popper-lsp-test");
+    let mut client = Client::start();
+
+    client.request("initialize", json!({ "capabilities": {} }));
+    client.notify("initialized", json!({}));
+
+    // Errors before the cursor, in an earlier item and earlier in the same block, do not
+    // stop the symbols in scope from being recorded.
+    let documents = [
+        (
+            "earlier_item.pop",
+            "func broken() -> int {\n    return \"text\";\n}\n\nfunc main() {\n    let x = broken();\n    \n}\n",
+            position(6, 4),
+            vec!["x", "main", "broken", "let", "if", "func", "return"],
+        ),
+        (
+            "earlier_statement.pop",
+            "func main() {\n    let a = unknown;\n    let b = 2;\n    \n}\n",
+            position(3, 4),
+            vec!["b", "a", "main", "let", "if", "func", "return"],
+        ),
+    ];
+    for (name, text, position, expected) in documents {
+        let uri = format!("file://{}", dir.join(name).display());
+        client.notify(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": uri, "languageId": "popper", "version": 1, "text": text } }),
+        );
+        // The diagnostics of every open document are published again, skip to this one.
+        let diagnostics = loop {
+            let diagnostics = client.notification("textDocument/publishDiagnostics");
+            if diagnostics["uri"] == uri {
+                break diagnostics;
+            }
+        };
+        assert_eq!(diagnostics["diagnostics"].as_array().unwrap().len(), 1, "{name}");
+
+        let items = client.request(
+            "textDocument/completion",
+            json!({ "textDocument": { "uri": uri }, "position": position }),
+        );
+        let labels: Vec<&str> = items.as_array().unwrap().iter().map(|item| item["label"].as_str().unwrap()).collect();
+        assert_eq!(labels, expected, "{name}");
+    }
+
+    client.request("shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.child.wait().unwrap().success());
+}
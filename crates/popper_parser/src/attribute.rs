@@ -1,10 +1,14 @@
-use popper_ast::attribute::Attribute;
+use popper_ast::attribute::{Attribute, LintLevel, ModuleAttributes};
 use popper_ast::token::TokenKind;
+use popper_error_core::lint;
 use crate::Parser;
 
 impl Parser {
     pub(crate) fn parse_attribute(&mut self) -> crate::error::Result<Attribute> {
         let ident = self.expect(TokenKind::Identifier)?;
+        if let Some(level) = LintLevel::from_name(&ident.value) {
+            return self.parse_lint_attribute(level);
+        }
         match ident.value.as_str() { 
             "C" => Ok(Attribute::StdCallC),
             _ => {
@@ -12,4 +16,64 @@ impl Parser {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Parses the `module(..);` statements at the start of a file into the module
+    /// attributes of the AST. Only lint attributes apply to a module.
+    ///
+    /// `module` is not a keyword: it only starts module attributes at the start of a file
+    /// and followed by `(`, anywhere else it is an identifier like any other.
+    pub(crate) fn parse_module_attributes(&mut self) -> crate::error::Result<()> {
+        while self.at_module_attributes()? {
+            let start = self.expect(TokenKind::Identifier)?;
+            self.expect(TokenKind::ParenL)?;
+            let mut attrs = Vec::new();
+            while !self.match_token(TokenKind::ParenR) {
+                if !attrs.is_empty() {
+                    self.expect(TokenKind::Comma)?;
+                }
+                let token = self.cursor.peek_token()?;
+                let attr = self.parse_attribute()?;
+                if !matches!(attr, Attribute::Lint(..)) {
+                    return Err(crate::parse_error!(expect a valid attribute but got (token)));
+                }
+                attrs.push(attr);
+            }
+            let end = self.expect_semicolon()?;
+            let span = start.span.merge(end.span);
+
+            match &mut self.ast.module_attrs {
+                Some(module_attrs) => {
+                    module_attrs.attrs.extend(attrs);
+                    module_attrs.span = module_attrs.span.merge(span);
+                }
+                None => self.ast.module_attrs = Some(ModuleAttributes { attrs, span }),
+            }
+        }
+        Ok(())
+    }
+
+    fn at_module_attributes(&mut self) -> crate::error::Result<bool> {
+        let token = self.cursor.peek_token()?;
+        if token.kind != TokenKind::Identifier || token.value != "module" {
+            return Ok(false);
+        }
+        Ok(self.cursor.peek_second_token()?.kind == TokenKind::ParenL)
+    }
+
+    /// Parses the lint list of `allow(..)`, `warn(..)` or `deny(..)`.
+    fn parse_lint_attribute(&mut self, level: LintLevel) -> crate::error::Result<Attribute> {
+        self.expect(TokenKind::ParenL)?;
+        let mut lints = Vec::new();
+        while !self.match_token(TokenKind::ParenR) {
+            if !lints.is_empty() {
+                self.expect(TokenKind::Comma)?;
+            }
+            let name = self.expect(TokenKind::Identifier)?;
+            if !lint::is_known(&name.value) {
+                return Err(crate::error::ParserError::unknown_lint(name.value, name.span));
+            }
+            lints.push(name.value);
+        }
+        Ok(Attribute::Lint(level, lints))
+    }
+}
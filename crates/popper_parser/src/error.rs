@@ -25,7 +25,7 @@ impl <T: Display> Display for FormatedListString<T> {
 
 #[derive(Diagnostics, Debug)]
 #[message("expected token `{0}` but found `{1}`", FormatedListString(expects_token.clone()), found_token.kind)]
-#[code = 3]
+#[code = 101]
 #[label = "found token `{found_token}`"]
 pub struct UnexpectedToken {
     expects_token: Vec<TokenKind>,
@@ -47,7 +47,7 @@ impl UnexpectedToken {
 
 #[derive(Diagnostics, Debug)]
 #[message("expected a valid attribute but found `{0}`", found_token.kind)]
-#[code = 4]
+#[code = 102]
 #[label = "found token `{found_token}`"]
 pub struct ExpectedValidAttribute {
     found_token: Token,
@@ -66,11 +66,85 @@ impl ExpectedValidAttribute {
 }
 
 
-#[derive(Debug)]
+#[derive(Diagnostics, Debug)]
+#[message("integer literal `{literal}` does not fit in `int`")]
+#[code = 103]
+#[label = "out of range for `int`"]
+#[note = "`int` is a 64-bit signed integer"]
+pub struct IntLiteralOutOfRange {
+    literal: String,
+
+    #[span]
+    pub span: Span,
+}
+
+impl IntLiteralOutOfRange {
+    pub fn new(literal: String, span: Span) -> Self {
+        IntLiteralOutOfRange { literal, span }
+    }
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("`pub` is only allowed on top-level items")]
+#[code = 104]
+#[label = "not allowed here"]
+#[note = "Items declared inside a function are never visible from other modules"]
+pub struct VisibilityNotAllowed {
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("unknown lint `{name}`")]
+#[code = 105]
+#[label = "unknown lint"]
+#[note = "Known lints are `unused_variables`, `unused_parameters`, `unused_functions` and `unreachable_code`, grouped as `unused` and `warnings`"]
+pub struct UnknownLint {
+    pub name: String,
+
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("expected `;` at the end of the line")]
+#[code = 106]
+#[label = "expected `;` here"]
+pub struct MissingSemicolon {
+    found_token: Token,
+
+    #[span]
+    pub span: Span,
+
+    /// The token found instead, `None` at the end of the file.
+    #[secondary_span]
+    #[secondary_label = "found `{found_token}` instead"]
+    pub found_span: Option<Span>,
+
+    #[suggestion(message = "insert", replacement = ";")]
+    pub insertion: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("`import` is only allowed at the top level of a module")]
+#[code = 107]
+#[label = "not allowed here"]
+#[note = "The symbols of an imported module are visible in the whole importing module"]
+pub struct ImportNotAllowed {
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Debug, Diagnostics)]
 pub enum ParserError {
     LexerError(LexerError),
     UnexpectedToken(UnexpectedToken),
     ExpectedValidAttribute(ExpectedValidAttribute),
+    IntLiteralOutOfRange(IntLiteralOutOfRange),
+    VisibilityNotAllowed(VisibilityNotAllowed),
+    UnknownLint(UnknownLint),
+    MissingSemicolon(MissingSemicolon),
+    ImportNotAllowed(ImportNotAllowed),
 }
 
 impl ParserError {
@@ -81,6 +155,32 @@ impl ParserError {
     pub fn expected_valid_attribute(found: Token, span: Span) -> Self {
         ParserError::ExpectedValidAttribute(ExpectedValidAttribute::new(found, span))
     }
+
+    pub fn int_literal_out_of_range(literal: String, span: Span) -> Self {
+        ParserError::IntLiteralOutOfRange(IntLiteralOutOfRange::new(literal, span))
+    }
+
+    pub fn visibility_not_allowed(span: Span) -> Self {
+        ParserError::VisibilityNotAllowed(VisibilityNotAllowed { span })
+    }
+
+    pub fn import_not_allowed(span: Span) -> Self {
+        ParserError::ImportNotAllowed(ImportNotAllowed { span })
+    }
+
+    pub fn unknown_lint(name: String, span: Span) -> Self {
+        ParserError::UnknownLint(UnknownLint { name, span })
+    }
+
+    /// `span` is the end of the token after which the `;` is missing.
+    pub fn missing_semicolon(found: Token, span: Span) -> Self {
+        ParserError::MissingSemicolon(MissingSemicolon {
+            found_span: (found.kind != TokenKind::Eof).then_some(found.span),
+            found_token: found,
+            span,
+            insertion: span,
+        })
+    }
     
     
     
@@ -89,6 +189,11 @@ impl ParserError {
             ParserError::LexerError(err) => err.span(),
             ParserError::UnexpectedToken(err) => err.span,
             ParserError::ExpectedValidAttribute(err) => err.span,
+            ParserError::IntLiteralOutOfRange(err) => err.span,
+            ParserError::VisibilityNotAllowed(err) => err.span,
+            ParserError::UnknownLint(err) => err.span,
+            ParserError::MissingSemicolon(err) => err.span,
+            ParserError::ImportNotAllowed(err) => err.span,
         }
     }
 }
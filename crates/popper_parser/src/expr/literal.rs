@@ -9,9 +9,19 @@ impl Parser {
         let token = self.expect(TokenKind::Number)?;
         let span = token.span;
 
+        let digits = token.value.replace('_', "");
+        let (radix, digits) = match digits.get(..2) {
+            Some("0x" | "0X") => (16, &digits[2..]),
+            Some("0o" | "0O") => (8, &digits[2..]),
+            Some("0b" | "0B") => (2, &digits[2..]),
+            _ => (10, digits.as_str()),
+        };
+        let value = i64::from_str_radix(digits, radix)
+            .map_err(|_| ParserError::int_literal_out_of_range(token.value.clone(), span))?;
+
         let node = LangNode {
             kind: LangNodeKind::Expr(
-                Expr::Int(token.value.parse().unwrap())
+                Expr::Int(value)
             ),
             span,
         };
@@ -49,13 +59,28 @@ impl Parser {
         
     }
 
+    fn parse_char_literal(&mut self) -> crate::error::Result<LangNodeId> {
+        let token = self.expect(TokenKind::Char)?;
+        let span = token.span;
+        let value = token.value.chars().next().unwrap_or_default();
+        let node = LangNode {
+            kind: LangNodeKind::Expr(
+                Expr::Char(value)
+            ),
+            span,
+        };
+
+        Ok(self.ast.add(node))
+    }
+
     pub(crate) fn parse_literal(&mut self) -> crate::error::Result<LangNodeId> {
         let token = self.cursor.peek_token()?;
         match token.kind {
             TokenKind::Number => self.parse_int_literal(),
             TokenKind::Identifier => self.parse_ident_literal(),
             TokenKind::String => self.parse_string_literal(),
-            _ => Err(parse_error!(expect tokens [Number, Identifier] but got (token))),
+            TokenKind::Char => self.parse_char_literal(),
+            _ => Err(parse_error!(expect tokens [Number, Identifier, String, Char] but got (token))),
         }
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use popper_ast::ast::{Expr, LangNode, LangNodeKind, UnaryOpKind};
+use popper_ast::ast::{Expr, LangNode, LangNodeKind, Span, UnaryOpKind};
 use popper_ast::layer::Ast;
 use popper_ast::token::TokenKind;
 use crate::Parser;
@@ -47,7 +47,7 @@ impl Parser {
     }
 
     pub(crate) fn parse_factor(&mut self) -> crate::error::Result<popper_ast::ast::LangNodeId> {
-        let literal = self.parse_unary_expr()?;
+        let literal = self.parse_cast_expr()?;
         let mut current = literal;
 
         loop {
@@ -88,6 +88,26 @@ impl Parser {
         Ok(current)
     }
     
+    pub(crate) fn parse_cast_expr(&mut self) -> crate::error::Result<popper_ast::ast::LangNodeId> {
+        let mut current = self.parse_unary_expr()?;
+
+        while self.match_token(TokenKind::KeywordAs) {
+            let ty = self.parse_ty()?;
+            let node = LangNode {
+                kind: LangNodeKind::Expr(
+                    Expr::Cast(
+                        current,
+                        ty,
+                    )
+                ),
+                span: Span::new(self.ast.get(current).span.lo, self.cursor.pos()),
+            };
+            current = self.ast.add(node);
+        }
+
+        Ok(current)
+    }
+
     pub(crate) fn parse_unary_expr(&mut self) -> crate::error::Result<popper_ast::ast::LangNodeId> {
         let token = self.cursor.peek_token()?;
         match token.kind {
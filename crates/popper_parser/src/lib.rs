@@ -5,6 +5,7 @@ mod ty;
 mod attribute;
 
 use popper_ast::ast::{LangAst, LangNode, LangNodeKind, Span};
+use popper_ast::cst::Cst;
 use popper_ast::file::SourceFileInfo;
 use popper_ast::layer::Ast;
 use popper_ast::token::{Token, TokenKind};
@@ -28,6 +29,11 @@ impl Parser {
         }
     }
 
+    /// The comments of the source parsed so far, which are not part of the AST.
+    pub fn comments(&self) -> &[Token] {
+        self.cursor.comments()
+    }
+
     fn match_token(&mut self, expected: TokenKind) -> bool {
         if let Ok(token) = self.cursor.peek_token() {
             if token.kind == expected {
@@ -55,6 +61,24 @@ impl Parser {
         )
     }
 
+    /// Expects the `;` ending a statement. When the next token is on a later line, reports
+    /// `MissingSemicolon` at the end of the previous token rather than at the next token.
+    fn expect_semicolon(&mut self) -> error::Result<Token> {
+        let token = self.cursor.peek_token()?;
+        if token.kind != TokenKind::Semicolon {
+            let before = &self.cursor.source()[..token.span.lo];
+            let mut end = before.trim_end().len();
+            // The `;` goes before the comments ending the line.
+            while let Some(comment) = self.cursor.comments().iter().find(|comment| comment.span.hi == end) {
+                end = before[..comment.span.lo].trim_end().len();
+            }
+            if before[end..].contains('\n') {
+                return Err(error::ParserError::missing_semicolon(token, Span::new(end, end)));
+            }
+        }
+        self.expect(TokenKind::Semicolon)
+    }
+
     fn expect_after(&mut self, expected: TokenKind) -> error::Result<Token> {
         let next_token = self.cursor.next_token()?;
         if next_token.kind == expected {
@@ -76,13 +100,14 @@ impl Parser {
     
     pub fn parse(&mut self) -> error::Result<LangAst> {
         let start = self.cursor.pos();
+        self.parse_module_attributes()?;
         let mut block = Vec::new();
         while !self.cursor.is_at_end() {
             let token = self.cursor.peek_token()?;
             if token.kind == TokenKind::Eof {
                 break;
             }
-            block.push(self.parse_stmt(true)?);
+            block.push(self.parse_item()?);
         }
         let end = self.cursor.pos();
         let node = LangNode {
@@ -95,3 +120,12 @@ impl Parser {
         Ok(self.ast.clone())
     }
 }
+
+/// Parses `source` into its AST and its CST, which keeps the whitespace and comments
+/// the AST leaves out.
+pub fn parse_lossless(source: &str) -> error::Result<(LangAst, Cst)> {
+    let ast = Parser::new(source).parse()?;
+    let tokens = popper_lexer::tokenize_lossless(source)?;
+    let cst = Cst::create_from_ast(&ast, tokens);
+    Ok((ast, cst))
+}
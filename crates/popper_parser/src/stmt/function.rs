@@ -1,4 +1,4 @@
-use popper_ast::ast::{ArgumentParamDef, Ident, LangNode, LangNodeId, LangNodeKind, Span};
+use popper_ast::ast::{ArgumentParamDef, Ident, LangNode, LangNodeId, LangNodeKind, Span, Visibility};
 use popper_ast::layer::Ast;
 use popper_ast::type_::Type;
 use popper_ast::token::TokenKind;
@@ -6,7 +6,7 @@ use crate::Parser;
 
 impl Parser {
     
-    pub(crate) fn parse_function_stmt(&mut self) -> crate::error::Result<LangNodeId> {
+    pub(crate) fn parse_function_stmt(&mut self, visibility: Visibility) -> crate::error::Result<LangNodeId> {
         let start = self.expect(TokenKind::KeywordFunc)?;
         let mut attributes = vec![];
         if self.match_token(TokenKind::ParenL) {
@@ -36,6 +36,8 @@ impl Parser {
                 ArgumentParamDef {
                     name: ident,
                     ty,
+                    span: Span::new(param.span.lo, self.cursor.pos()),
+                    name_span: param.span,
                 }
             );
             
@@ -60,6 +62,8 @@ impl Parser {
         let node = LangNode {
             kind: LangNodeKind::FunctionDef {
                 name: Ident(self.ast.add_symbol(&identifier.value)),
+                name_span: identifier.span,
+                visibility,
                 attrs: attributes,
                 params: parameters,
                 ret: return_type,
@@ -77,7 +81,7 @@ impl Parser {
     pub(crate) fn parse_return(&mut self) -> crate::error::Result<LangNodeId> {
         let start = self.expect(TokenKind::KeywordReturn)?;
         let expr = self.parse_expr()?;
-        let end = self.expect(TokenKind::Semicolon)?;
+        let end = self.expect_semicolon()?;
         
         let node = LangNode {
             kind: LangNodeKind::Return(expr),
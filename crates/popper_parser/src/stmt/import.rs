@@ -0,0 +1,26 @@
+use popper_ast::ast::{Ident, LangNode, LangNodeId, LangNodeKind};
+use popper_ast::layer::Ast;
+use popper_ast::token::TokenKind;
+use crate::Parser;
+use crate::error::Result;
+
+impl Parser {
+    pub(crate) fn parse_import_stmt(&mut self) -> Result<LangNodeId> {
+        let start = self.expect(TokenKind::KeywordImport)?;
+        let mut path = Vec::new();
+        loop {
+            let segment = self.expect(TokenKind::Identifier)?;
+            path.push(Ident(self.ast.add_symbol(&segment.value)));
+            if !self.match_token(TokenKind::Dot) {
+                break;
+            }
+        }
+        let end = self.expect_semicolon()?;
+
+        let node = LangNode {
+            kind: LangNodeKind::Import(path),
+            span: start.span.merge(end.span),
+        };
+        Ok(self.ast.add(node))
+    }
+}
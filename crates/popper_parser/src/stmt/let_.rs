@@ -1,15 +1,15 @@
-use popper_ast::ast::{Ident, Let, LangNode, LangNodeId, LangNodeKind};
+use popper_ast::ast::{Ident, Let, LangNode, LangNodeId, LangNodeKind, Visibility};
 use popper_ast::layer::Ast;
 use popper_ast::token::TokenKind;
 use crate::Parser;
 use crate::error::Result;
 impl Parser {
-    pub(crate) fn parse_let_stmt(&mut self) -> Result<LangNodeId> {
+    pub(crate) fn parse_let_stmt(&mut self, visibility: Visibility) -> Result<LangNodeId> {
         let start = self.expect(TokenKind::KeywordLet)?;
         let identifier = self.expect(TokenKind::Identifier)?;
         self.expect(TokenKind::Eq)?;
         let expr = self.parse_expr()?;
-        let end = self.expect(TokenKind::Semicolon)?;
+        let end = self.expect_semicolon()?;
 
         let span = start.span.merge(end.span);
 
@@ -19,6 +19,8 @@ impl Parser {
             kind: LangNodeKind::Let(
                 Let {
                     name: Ident(symbol),
+                    name_span: identifier.span,
+                    visibility,
                     value: expr,
                 }
             ),
@@ -1,4 +1,4 @@
-use popper_ast::ast::LangNodeId;
+use popper_ast::ast::{LangNodeId, Visibility};
 use popper_ast::token::TokenKind;
 use crate::Parser;
 
@@ -6,27 +6,54 @@ mod let_;
 mod if_;
 mod function;
 mod block;
+mod import;
 
 impl Parser {
+    /// Parses a top-level statement, the only place where `pub` and `import` are allowed.
+    pub(crate) fn parse_item(&mut self) -> crate::error::Result<LangNodeId> {
+        let token = self.cursor.peek_token()?;
+        if token.kind == TokenKind::KeywordImport {
+            return self.parse_import_stmt();
+        }
+        if !self.match_token(TokenKind::KeywordPub) {
+            return self.parse_stmt(true);
+        }
+
+        let item = self.cursor.peek_token()?;
+        let id = match item.kind {
+            TokenKind::KeywordFunc => self.parse_function_stmt(Visibility::Public)?,
+            TokenKind::KeywordLet => self.parse_let_stmt(Visibility::Public)?,
+            _ => return Err(crate::parse_error!(expect tokens [KeywordFunc, KeywordLet] but got (item))),
+        };
+        self.ast.get_mut(id).span.lo = token.span.lo;
+        Ok(id)
+    }
+
     pub fn parse_stmt(&mut self, expr_terminated: bool) -> crate::error::Result<LangNodeId> {
         let token = self.cursor.peek_token()?;
         match token.kind {
             TokenKind::KeywordLet => {
-                self.parse_let_stmt()
+                self.parse_let_stmt(Visibility::Private)
             }
             TokenKind::KeywordIf => {
                 self.parse_if_stmt()
             }
             TokenKind::KeywordFunc => {
-                self.parse_function_stmt()
+                self.parse_function_stmt(Visibility::Private)
             }
             TokenKind::KeywordReturn => {
                 self.parse_return()
             }
+            TokenKind::KeywordImport => {
+                Err(crate::error::ParserError::import_not_allowed(token.span))
+            }
+            TokenKind::KeywordPub => {
+                Err(crate::error::ParserError::visibility_not_allowed(token.span))
+            }
             _ => {
                 let res = self.parse_expr()?;
                 if expr_terminated {
-                    self.expect(TokenKind::Semicolon)?;
+                    self.expect_semicolon()?;
                 } else {
                     self.expect(TokenKind::Newline)?;
                 }
@@ -23,13 +23,17 @@ impl Parser {
                 self.cursor.next_token()?;
                 Ok(Type::String)
             }
+            TokenKind::TypeChar => {
+                self.cursor.next_token()?;
+                Ok(Type::Char)
+            }
             TokenKind::TypeVoid => {
                 self.cursor.next_token()?;
                 Ok(Type::Void)
             }
             _ => {
                 Err(crate::parse_error!(
-                    expect tokens [TypeInt, TypeFloat, TypeBool, TypeString, TypeVoid] but got (token)
+                    expect tokens [TypeInt, TypeFloat, TypeBool, TypeString, TypeChar, TypeVoid] but got (token)
                 ))
             }
         }
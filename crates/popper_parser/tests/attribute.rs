@@ -0,0 +1,29 @@
+use popper_error_core::Diagnostics;
+use popper_parser::Parser;
+
+#[test]
+fn test_module_attributes() {
+    let ast = Parser::new("// lints\nmodule(allow(unused), deny(unused_variables));\nmodule(warn(unused_functions));\nlet x = 1;")
+        .parse()
+        .unwrap();
+    assert_eq!(
+        ast.dumps(),
+        "Module(allow(unused),deny(unused_variables),warn(unused_functions))\nLet x = Int(1)\n"
+    );
+
+    let error = Parser::new("module(C);").parse().unwrap_err();
+    assert_eq!(error.code(), 102);
+}
+
+#[test]
+fn test_module_is_not_a_keyword() {
+    // Only `module(` at the start of a file starts module attributes.
+    let ast = Parser::new("let module = 1;\nfunc f(module: int) -> int = module\n").parse().unwrap();
+    assert!(ast.module_attrs.is_none());
+    assert_eq!(ast.dumps(), "Let module = Int(1)\nFunctionDef() f(0: Int) -> Int = Ident(module)\n");
+
+    // After the first item, `module(..)` is a call.
+    let ast = Parser::new("let x = 1;\nmodule(x);").parse().unwrap();
+    assert!(ast.module_attrs.is_none());
+    assert_eq!(ast.dumps(), "Let x = Int(1)\nIdent(module).call([Ident(x)])\n");
+}
@@ -0,0 +1,51 @@
+use popper_ast::ast::{Expr, LangNodeKind};
+use popper_ast::cst::CstElement;
+use popper_ast::layer::Ast;
+use popper_parser::parse_lossless;
+use std::path::Path;
+
+#[test]
+fn test_cst_round_trip() {
+    let sources = [
+        "",
+        "\n\n  // only a comment",
+        "func(C)  puts(s: string);\r\n\r\nfunc main() {\n\tputs(r#\"a \"b\"\"#); // call\n    let x = -1 +\n        2 * 3 as int;\n  x;\n}\n// end",
+        "pub func f(a: int) -> int = a + 1   \n\nlet y = f('\\n');",
+    ];
+    for source in sources {
+        let (_, cst) = parse_lossless(source).unwrap();
+        assert_eq!(cst.text(cst.root()), source);
+    }
+
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples");
+    for entry in std::fs::read_dir(examples).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|extension| extension == "pop") {
+            let source = std::fs::read_to_string(&path).unwrap();
+            let (_, cst) = parse_lossless(&source).unwrap();
+            assert_eq!(cst.text(cst.root()), source, "{}", path.display());
+        }
+    }
+}
+
+#[test]
+fn test_cst_nodes() {
+    let source = "func main() {\n    let x = 1 + 2; // three\n    x;\n}\n";
+    let (ast, cst) = parse_lossless(source).unwrap();
+
+    let let_ = ast
+        .nodes()
+        .find(|&id| matches!(ast.get(id).kind, LangNodeKind::Let(_)))
+        .unwrap();
+    assert_eq!(cst.text(let_.into()), "    let x = 1 + 2; // three\n");
+    let children = &cst.get(let_.into()).children;
+    assert_eq!(children.len(), 5);
+    assert!(matches!(&children[3], CstElement::Node(value) if cst.text(*value) == "1 + 2"));
+
+    let sum = ast
+        .nodes()
+        .find(|&id| matches!(ast.get(id).kind, LangNodeKind::Expr(Expr::Add(..))))
+        .unwrap();
+    let tokens: Vec<&str> = cst.tokens(sum.into()).iter().map(|token| token.token.value.as_str()).collect();
+    assert_eq!(tokens, ["1", "+", "2"]);
+}
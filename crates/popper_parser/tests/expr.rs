@@ -0,0 +1,16 @@
+use popper_parser::Parser;
+
+fn dumps(source: &str) -> String {
+    Parser::new(source).parse().unwrap().dumps()
+}
+
+#[test]
+fn test_cast_precedence() {
+    // `as` binds tighter than the binary operators, and looser than the unary ones.
+    assert_eq!(
+        dumps("a + b as float * c as int;"),
+        "Add(Ident(a), Mul(Cast(Ident(b), float), Cast(Ident(c), int)))\n"
+    );
+    assert_eq!(dumps("a as int + b;"), "Add(Cast(Ident(a), int), Ident(b))\n");
+    assert_eq!(dumps("-a as float;"), "Cast(UnaryOp(-, Ident(a)), float)\n");
+}
@@ -0,0 +1,53 @@
+use popper_ast::ast::Span;
+use popper_error_core::Diagnostics;
+use popper_parser::Parser;
+
+/// Parses `source`, which misses a `;`, and returns the source fixed by the suggestion,
+/// checking that it parses.
+fn fix(source: &str) -> String {
+    let error = Parser::new(source).parse().unwrap_err();
+    assert_eq!(error.code(), 106, "{source}");
+    let suggestions = error.suggestions();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].span, error.span());
+    let mut fixed = source.to_string();
+    fixed.replace_range(suggestions[0].span.lo..suggestions[0].span.hi, &suggestions[0].replacement);
+    assert!(Parser::new(&fixed).parse().is_ok(), "{fixed}");
+    fixed
+}
+
+#[test]
+fn test_missing_semicolon() {
+    // The error is at the end of the line, pointing at the token found on the next one.
+    let source = "let x = 1\nlet y = 2;";
+    let error = Parser::new(source).parse().unwrap_err();
+    assert_eq!(error.span(), Span::new(9, 9));
+    let labels = error.secondary_labels();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].span, Span::new(10, 13));
+    assert_eq!(fix(source), "let x = 1;\nlet y = 2;");
+
+    // The `;` goes before trailing whitespace and comments.
+    assert_eq!(
+        fix("let x = 1   // one\n  // two\nlet y = 2;"),
+        "let x = 1;   // one\n  // two\nlet y = 2;"
+    );
+    assert_eq!(
+        fix("func main() {\n    puts(\"a\")\n    puts(\"b\");\n}"),
+        "func main() {\n    puts(\"a\");\n    puts(\"b\");\n}"
+    );
+
+    // At the end of the file, there is no token to point at.
+    let error = Parser::new("let x = 1\n").parse().unwrap_err();
+    assert!(error.secondary_labels().is_empty());
+    assert_eq!(fix("let x = 1\n"), "let x = 1;\n");
+}
+
+#[test]
+fn test_missing_semicolon_same_line() {
+    // Without a line break, the next token is unexpected.
+    let error = Parser::new("let x = 1 let y = 2;").parse().unwrap_err();
+    assert_eq!(error.code(), 101);
+    assert_eq!(error.span(), Span::new(10, 13));
+    assert!(error.suggestions().is_empty());
+}
@@ -0,0 +1,66 @@
+use popper_ast::ast::{Expr, LangAst, LangNodeId, LangNodeKind, Let};
+use popper_ast::fold::{self, Folder};
+use popper_ast::visit::{self, Visitor};
+use popper_parser::Parser;
+
+/// Collects the names of the variables used, and the ones bound by `let`.
+#[derive(Default)]
+struct Names {
+    used: Vec<String>,
+    bound: Vec<String>,
+}
+
+impl<'ast> Visitor<'ast> for Names {
+    fn visit_expr(&mut self, ast: &'ast LangAst, id: LangNodeId, expr: &'ast Expr) {
+        if let Expr::Ident(name) = expr {
+            self.used.push(ast.get_symbol(name.0).name.clone());
+        }
+        visit::walk_expr(self, ast, id, expr)
+    }
+
+    fn visit_let(&mut self, ast: &'ast LangAst, id: LangNodeId, let_: &'ast Let) {
+        self.bound.push(ast.get_symbol(let_.name.0).name.clone());
+        visit::walk_let(self, ast, id, let_)
+    }
+}
+
+#[test]
+fn test_visitor() {
+    let source = "func f(a: int) -> int { let b = a + 1; if g(b) { return -b; } else { return a as int; } }";
+    let ast = Parser::new(source).parse().unwrap();
+
+    let mut names = Names::default();
+    names.visit_node(&ast, ast.root);
+    assert_eq!(names.used, ["a", "g", "b", "b", "a"]);
+    assert_eq!(names.bound, ["b"]);
+}
+
+/// Replaces `x + 0` with `x`.
+struct AddZero;
+
+impl Folder for AddZero {
+    fn fold_expr(&mut self, ast: &mut LangAst, id: LangNodeId, expr: Expr) -> LangNodeId {
+        let id = fold::walk_expr(self, ast, id, expr);
+        match ast.get(id).kind {
+            LangNodeKind::Expr(Expr::Add(lhs, rhs))
+                if matches!(ast.get(rhs).kind, LangNodeKind::Expr(Expr::Int(0))) =>
+            {
+                lhs
+            }
+            _ => id,
+        }
+    }
+}
+
+#[test]
+fn test_folder() {
+    let mut ast = Parser::new("let x = 1; func f() -> int { return g(x + 0 + 0) + 0; }")
+        .parse()
+        .unwrap();
+    let root = ast.root;
+    let root = AddZero.fold_node(&mut ast, root);
+    ast.set_root(root);
+
+    let expected = Parser::new("let x = 1; func f() -> int { return g(x); }").parse().unwrap();
+    assert_eq!(ast.dumps(), expected.dumps());
+}
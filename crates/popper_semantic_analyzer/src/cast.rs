@@ -0,0 +1,39 @@
+use popper_ast::type_::Type;
+
+/// How a value is converted by an `as` expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastKind {
+    /// The source and target types are the same, the value is left untouched.
+    NoOp,
+    /// `int as float`
+    IntToFloat,
+    /// `float as int`, truncating towards zero
+    FloatToInt,
+    /// `bool as int`, `true` becomes `1` and `false` becomes `0`
+    BoolToInt,
+    /// `char as int`, the Unicode scalar value of the character
+    CharToInt,
+    /// `int as char`, keeping the low 32 bits of the value
+    IntToChar,
+}
+
+/// Every cast accepted by the type checker, as `(from, to, kind)`.
+const CAST_RULES: &[(Type, Type, CastKind)] = &[
+    (Type::Int, Type::Int, CastKind::NoOp),
+    (Type::Float, Type::Float, CastKind::NoOp),
+    (Type::Bool, Type::Bool, CastKind::NoOp),
+    (Type::Char, Type::Char, CastKind::NoOp),
+    (Type::Int, Type::Float, CastKind::IntToFloat),
+    (Type::Float, Type::Int, CastKind::FloatToInt),
+    (Type::Bool, Type::Int, CastKind::BoolToInt),
+    (Type::Char, Type::Int, CastKind::CharToInt),
+    (Type::Int, Type::Char, CastKind::IntToChar),
+];
+
+/// Returns how `from` is converted to `to`, or `None` if the cast is not allowed.
+pub fn cast_kind(from: &Type, to: &Type) -> Option<CastKind> {
+    CAST_RULES
+        .iter()
+        .find(|(rule_from, rule_to, _)| rule_from == from && rule_to == to)
+        .map(|(_, _, kind)| *kind)
+}
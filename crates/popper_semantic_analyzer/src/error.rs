@@ -1,18 +1,67 @@
-use popper_error_core::Diagnostics;
+use popper_error_core::{Diagnostics, Suggestion};
 use popper_error_macro::Diagnostics;
 use popper_ast::ast::Span;
+use popper_ast::file::FileId;
 use popper_ast::type_::Type;
 
-#[derive(Diagnostics, Debug)]
-#[message("Symbol `{name}` not found")]
-#[code = 1001]
-#[label = "Symbol not found"]
-#[note = "Ensure the symbol is declared before use"]
+#[derive(Debug)]
 pub struct SymbolNotFound {
     pub name: String,
-    
-    #[span]
     pub span: Span,
+    /// A visible symbol spelled like `name`, suggested as a replacement.
+    pub similar: Option<String>,
+    /// A keyword spelled like `name`, when no visible symbol is. Only mentioned in the
+    /// help, as replacing `name` with it would not be valid.
+    pub keyword: Option<String>,
+    /// The import path of a module defining `name` that is not imported.
+    pub module: Option<String>,
+}
+
+const _: () = popper_error_core::registry::check_code(1001, "popper_semantic_analyzer::SymbolNotFound");
+
+impl Diagnostics for SymbolNotFound {
+    fn message(&self) -> String {
+        format!("Symbol `{}` not found", self.name)
+    }
+
+    fn code(&self) -> u32 {
+        1001
+    }
+
+    fn label(&self) -> String {
+        "Symbol not found".to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn note(&self) -> Option<String> {
+        Some("Ensure the symbol is declared before use".to_string())
+    }
+
+    fn help(&self) -> Option<String> {
+        if let Some(module) = &self.module {
+            return Some(format!(
+                "`{}` is defined in module `{module}`, add `import {module};` to use it",
+                self.name
+            ));
+        }
+        self.keyword
+            .as_ref()
+            .map(|keyword| format!("`{keyword}` is a keyword with a similar name"))
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        self.similar
+            .iter()
+            .map(|similar| Suggestion {
+                message: "did you mean".to_string(),
+                span: self.span,
+                replacement: similar.clone(),
+            })
+            .collect()
+    }
 }
 
 #[derive(Diagnostics, Debug)]
@@ -26,6 +75,11 @@ pub struct TypeMismatch {
 
     #[span]
     pub span: Span,
+
+    /// The parameter declaration, when the mismatch is on a call argument.
+    #[secondary_span]
+    #[secondary_label = "parameter declared as `{expected}` here"]
+    pub parameter: Option<Span>,
 }
 
 #[derive(Diagnostics, Debug)]
@@ -64,6 +118,126 @@ pub struct ReturnNotInFunction {
     pub span: Span,
 }
 
+#[derive(Diagnostics, Debug)]
+#[message("Invalid cast from `{from}` to `{to}`")]
+#[code = 1006]
+#[label = "cannot cast `{from}` as `{to}`"]
+#[note = "Only casts between `int` and `float`, between `int` and `char`, and from `bool` to `int` are allowed"]
+pub struct InvalidCast {
+    pub from: Type,
+    pub to: Type,
+
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("Symbol `{name}` is private")]
+#[code = 1007]
+#[label = "private symbol"]
+#[help = "Mark `{name}` as `pub` in its module to use it here"]
+pub struct SymbolIsPrivate {
+    pub name: String,
+
+    #[span]
+    pub span: Span,
+
+    pub definition_file: FileId,
+    #[secondary_span(file = definition_file)]
+    #[secondary_label = "`{name}` is defined here"]
+    pub definition_span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("`{name}` is defined multiple times")]
+#[code = 1008]
+#[label = "`{name}` redefined here"]
+#[help = "Rename one of the definitions, or shadow it with `let` in a nested block"]
+pub struct DuplicateDefinition {
+    pub name: String,
+
+    #[span]
+    pub span: Span,
+
+    #[secondary_span]
+    #[secondary_label = "previous definition of `{name}` here"]
+    pub previous_span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("Not all paths of `{name}` return a value")]
+#[code = 1009]
+#[label = "`{name}` can reach its end without returning `{ret}`"]
+#[note = "Add a `return` statement on every path of the function"]
+pub struct MissingReturn {
+    pub name: String,
+    pub ret: Type,
+
+    #[span]
+    pub span: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("Unreachable statement")]
+#[code = 1010]
+#[label = "this statement is never executed"]
+#[warning]
+#[lint = "unreachable_code"]
+pub struct UnreachableCode {
+    #[span]
+    pub span: Span,
+
+    #[secondary_span]
+    #[secondary_label = "any code following this statement is unreachable"]
+    pub cause: Span,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("Unused variable `{name}`")]
+#[code = 1011]
+#[label = "`{name}` is never used"]
+#[warning]
+#[lint = "unused_variables"]
+pub struct UnusedVariable {
+    pub name: String,
+
+    #[span]
+    pub span: Span,
+
+    #[suggestion(message = "if this is intentional, prefix it with an underscore", replacement = "_{name}")]
+    pub name_span: Option<Span>,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("Unused parameter `{name}`")]
+#[code = 1012]
+#[label = "`{name}` is never used"]
+#[warning]
+#[lint = "unused_parameters"]
+pub struct UnusedParameter {
+    pub name: String,
+
+    #[span]
+    pub span: Span,
+
+    #[suggestion(message = "if this is intentional, prefix it with an underscore", replacement = "_{name}")]
+    pub name_span: Option<Span>,
+}
+
+#[derive(Diagnostics, Debug)]
+#[message("Unused function `{name}`")]
+#[code = 1013]
+#[label = "`{name}` is never called"]
+#[note = "Prefix it with an underscore (`_{name}`) or mark it `pub` to silence this warning"]
+#[warning]
+#[lint = "unused_functions"]
+pub struct UnusedFunction {
+    pub name: String,
+
+    #[span]
+    pub span: Span,
+}
+
 #[derive(Debug, Diagnostics)]
 pub enum SemanticError {
     SymbolNotFound(SymbolNotFound),
@@ -71,17 +245,46 @@ pub enum SemanticError {
     NotAFunction(NotAFunction),
     ArgumentCountMismatch(ArgumentCountMismatch),
     ReturnNotInFunction(ReturnNotInFunction),
+    InvalidCast(InvalidCast),
+    SymbolIsPrivate(SymbolIsPrivate),
+    DuplicateDefinition(DuplicateDefinition),
+    MissingReturn(MissingReturn),
+    UnreachableCode(UnreachableCode),
+    UnusedVariable(UnusedVariable),
+    UnusedParameter(UnusedParameter),
+    UnusedFunction(UnusedFunction),
 }
 
 impl SemanticError {
     pub fn symbol_not_found(name: String, span: Span) -> Self {
-        SemanticError::SymbolNotFound(SymbolNotFound { name, span })
+        SemanticError::SymbolNotFound(SymbolNotFound {
+            name,
+            span,
+            similar: None,
+            keyword: None,
+            module: None,
+        })
     }
     pub fn type_mismatch(expected: String, found: String, span: Span) -> Self {
         SemanticError::TypeMismatch(TypeMismatch {
             expected,
             found,
             span,
+            parameter: None,
+        })
+    }
+
+    pub fn argument_type_mismatch(
+        expected: String,
+        found: String,
+        span: Span,
+        parameter: Option<Span>,
+    ) -> Self {
+        SemanticError::TypeMismatch(TypeMismatch {
+            expected,
+            found,
+            span,
+            parameter,
         })
     }
     
@@ -100,6 +303,60 @@ impl SemanticError {
     pub fn return_not_in_function(span: Span) -> Self {
         SemanticError::ReturnNotInFunction(ReturnNotInFunction { span })
     }
+
+    pub fn invalid_cast(from: Type, to: Type, span: Span) -> Self {
+        SemanticError::InvalidCast(InvalidCast { from, to, span })
+    }
+
+    pub fn symbol_is_private(
+        name: String,
+        span: Span,
+        definition_file: FileId,
+        definition_span: Span,
+    ) -> Self {
+        SemanticError::SymbolIsPrivate(SymbolIsPrivate {
+            name,
+            span,
+            definition_file,
+            definition_span,
+        })
+    }
+
+    pub fn duplicate_definition(name: String, span: Span, previous_span: Span) -> Self {
+        SemanticError::DuplicateDefinition(DuplicateDefinition {
+            name,
+            span,
+            previous_span,
+        })
+    }
+
+    pub fn missing_return(name: String, ret: Type, span: Span) -> Self {
+        SemanticError::MissingReturn(MissingReturn { name, ret, span })
+    }
+
+    pub fn unreachable_code(span: Span, cause: Span) -> Self {
+        SemanticError::UnreachableCode(UnreachableCode { span, cause })
+    }
+
+    pub fn unused_variable(name: String, span: Span, name_span: Option<Span>) -> Self {
+        SemanticError::UnusedVariable(UnusedVariable {
+            name,
+            span,
+            name_span,
+        })
+    }
+
+    pub fn unused_parameter(name: String, span: Span, name_span: Option<Span>) -> Self {
+        SemanticError::UnusedParameter(UnusedParameter {
+            name,
+            span,
+            name_span,
+        })
+    }
+
+    pub fn unused_function(name: String, span: Span) -> Self {
+        SemanticError::UnusedFunction(UnusedFunction { name, span })
+    }
 }
 
 #[macro_export]
@@ -0,0 +1,89 @@
+use popper_ast::ast::{ArgumentParamDef, LangAst, LangNodeId, Span};
+use popper_ast::visit::Visitor;
+
+/// A statement that is never executed because every path before it returns.
+#[derive(Debug, Clone, Copy)]
+pub struct Unreachable {
+    pub span: Span,
+    /// The `return` (or the `if` whose branches all return) making it unreachable.
+    pub cause: Span,
+}
+
+/// The control flow of a function body.
+#[derive(Debug, Clone, Default)]
+pub struct Flow {
+    /// The statement every path through the body ends with, `None` if the body can
+    /// reach its end.
+    pub returns: Option<Span>,
+    pub unreachable: Vec<Unreachable>,
+}
+
+/// Analyzes the control flow of `body`. Nested function definitions are skipped, they
+/// are analyzed with their own body.
+pub fn analyze(ast: &LangAst, body: LangNodeId) -> Flow {
+    let mut visitor = FlowVisitor::default();
+    visitor.visit_node(ast, body);
+    Flow {
+        returns: visitor.returns,
+        unreachable: visitor.unreachable,
+    }
+}
+
+#[derive(Default)]
+struct FlowVisitor {
+    /// The span of the statement making the last node visited always return, if any.
+    returns: Option<Span>,
+    unreachable: Vec<Unreachable>,
+}
+
+impl FlowVisitor {
+    /// Visits `node`, returning the span of the statement making it always return.
+    fn returns(&mut self, ast: &LangAst, node: LangNodeId) -> Option<Span> {
+        self.returns = None;
+        self.visit_node(ast, node);
+        self.returns
+    }
+}
+
+impl<'ast> Visitor<'ast> for FlowVisitor {
+    fn visit_return(&mut self, ast: &'ast LangAst, id: LangNodeId, _value: LangNodeId) {
+        self.returns = Some(ast.get(id).span);
+    }
+
+    fn visit_block(&mut self, ast: &'ast LangAst, _id: LangNodeId, items: &'ast [LangNodeId]) {
+        let mut returns = None;
+        for &stmt in items {
+            if let Some(cause) = returns {
+                self.unreachable.push(Unreachable {
+                    span: ast.get(stmt).span,
+                    cause,
+                });
+                break;
+            }
+            returns = self.returns(ast, stmt);
+        }
+        self.returns = returns;
+    }
+
+    fn visit_if(
+        &mut self,
+        ast: &'ast LangAst,
+        id: LangNodeId,
+        _condition: LangNodeId,
+        then_branch: LangNodeId,
+        else_branch: Option<LangNodeId>,
+    ) {
+        let then_returns = self.returns(ast, then_branch);
+        let else_returns = else_branch.and_then(|branch| self.returns(ast, branch));
+        self.returns = then_returns.and(else_returns).map(|_| ast.get(id).span);
+    }
+
+    fn visit_function_def(
+        &mut self,
+        _ast: &'ast LangAst,
+        _id: LangNodeId,
+        _params: &'ast [ArgumentParamDef],
+        _body: Option<LangNodeId>,
+    ) {
+    }
+}
@@ -1,5 +1,8 @@
 
-use popper_ast::ast::{LangAst, LangNode, LangNodeId};
+use popper_ast::ast::{LangAst, LangNode, LangNodeId, Span};
+use crate::cast::CastKind;
+use crate::symbol_resolver::SymbolKind;
+use popper_ast::file::FileId;
 use popper_ast::layer::Ast;
 use popper_ast::type_::Type;
 use popper_index::Idx;
@@ -60,15 +63,59 @@ impl NodeDescriptor {
         self.descriptors.push(kind);
     }
 
+    pub fn set_definition(&mut self, definition: Definition) {
+        self.descriptors.retain(|descriptor| !matches!(descriptor, NodeDescriptorKind::Definition(_)));
+        self.descriptors.push(NodeDescriptorKind::Definition(definition));
+    }
+
+    pub fn get_definition(&self) -> Option<Definition> {
+        self.descriptors.iter().find_map(|descriptor| match descriptor {
+            NodeDescriptorKind::Definition(definition) => Some(*definition),
+            _ => None,
+        })
+    }
+
+    pub fn set_cast_kind(&mut self, kind: CastKind) {
+        self.descriptors.retain(|descriptor| !matches!(descriptor, NodeDescriptorKind::Cast(_)));
+        self.descriptors.push(NodeDescriptorKind::Cast(kind));
+    }
+
+    pub fn get_cast_kind(&self) -> Option<CastKind> {
+        self.descriptors.iter().find_map(|descriptor| match descriptor {
+            NodeDescriptorKind::Cast(kind) => Some(*kind),
+            _ => None,
+        })
+    }
+
     pub fn get(&self, id: NodeDescriptorId) -> &NodeDescriptorKind {
         &self.descriptors[id.index()]
     }
+
+    pub fn get_type(&self) -> Option<&Type> {
+        self.descriptors.iter().find_map(|descriptor| match descriptor {
+            NodeDescriptorKind::Type(ty) => Some(ty),
+            _ => None,
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum NodeDescriptorKind {
     Used(usize),
     Type(Type),
+    /// Where the symbol an identifier refers to is defined.
+    Definition(Definition),
+    /// How the value of an `as` expression is converted.
+    Cast(CastKind),
+}
+
+/// The definition of a symbol, as recorded by the symbol resolver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Definition {
+    /// The module the symbol is defined in, `None` for the current one.
+    pub file: Option<FileId>,
+    pub span: Span,
+    pub kind: SymbolKind,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -156,6 +203,41 @@ impl Hir {
         } 
     }
     
+    pub fn get_type(&self, id: HirNodeId) -> Option<&Type> {
+        let descriptor_id = self.nodes[id.index()].descriptor;
+        self.descriptors
+            .get(descriptor_id.index())
+            .and_then(|descriptor| descriptor.get_type())
+    }
+
+    pub fn get_definition(&self, id: HirNodeId) -> Option<Definition> {
+        let descriptor_id = self.nodes[id.index()].descriptor;
+        self.descriptors
+            .get(descriptor_id.index())
+            .and_then(|descriptor| descriptor.get_definition())
+    }
+
+    pub fn get_cast_kind(&self, id: HirNodeId) -> Option<CastKind> {
+        let descriptor_id = self.nodes[id.index()].descriptor;
+        self.descriptors
+            .get(descriptor_id.index())
+            .and_then(|descriptor| descriptor.get_cast_kind())
+    }
+
+    pub fn set_cast_kind(&mut self, id: HirNodeId, kind: CastKind) {
+        let descriptor_id = self.nodes[id.index()].descriptor;
+        if let Some(descriptor) = self.descriptors.get_mut(descriptor_id.index()) {
+            descriptor.set_cast_kind(kind);
+        }
+    }
+
+    pub fn set_definition(&mut self, id: HirNodeId, definition: Definition) {
+        let descriptor_id = self.nodes[id.index()].descriptor;
+        if let Some(descriptor) = self.descriptors.get_mut(descriptor_id.index()) {
+            descriptor.set_definition(definition);
+        }
+    }
+
     pub fn set_type(&mut self, id: HirNodeId, ty: Type) {
         let descriptor_id = self.nodes[id.index()].descriptor;
         if let Some(descriptor) = self.descriptors.get_mut(descriptor_id.index()) {
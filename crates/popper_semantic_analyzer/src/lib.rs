@@ -1,19 +1,27 @@
 #![feature(try_trait_v2)]
 
 use crate::hir::Hir;
+use crate::lint::LintScopes;
 use crate::symbol_resolver::SymbolResolver;
 use crate::type_checker::TypeChecker;
-use popper_ast::ast::{LangAst, LangNodeId};
+use popper_ast::ast::{LangAst, LangNodeId, SymbolId};
+use popper_ast::attribute::LintLevel;
 use popper_ast::layer::Layer;
 use popper_ast::type_::Type;
+use popper_error_core::Diagnostics;
 use std::convert::Infallible;
 use std::ops::{ControlFlow, FromResidual, Try};
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
+pub mod cast;
 mod error;
+pub mod flow;
 pub mod hir;
+mod lint;
+pub mod module;
+pub mod suggest;
 pub mod symbol_resolver;
 pub mod type_checker;
 mod typed;
@@ -152,6 +160,12 @@ pub struct SemanticAnalyzer {
     layers: Vec<SemanticLayerKind>,
     ast: LangAst,
     hir: Hir,
+    lint_scopes: LintScopes,
+    /// Warnings with the level set by the attributes around them.
+    warnings: Vec<(error::SemanticError, Option<LintLevel>)>,
+    /// The import path and public symbols of the other modules of the program that
+    /// this one does not import.
+    unimported_modules: Vec<(String, Vec<String>)>,
 }
 
 impl SemanticAnalyzer {
@@ -159,10 +173,52 @@ impl SemanticAnalyzer {
         SemanticAnalyzer {
             layers: vec![],
             hir: Hir::create_from_ast(&ast),
+            lint_scopes: LintScopes::from_ast(&ast),
             ast,
+            warnings: Vec::new(),
+            unimported_modules: Vec::new(),
         }
     }
 
+    pub fn ast(&self) -> &LangAst {
+        &self.ast
+    }
+
+    pub fn hir(&self) -> &Hir {
+        &self.hir
+    }
+
+    /// Looks `id` up in the scopes of the symbol resolver layer.
+    pub fn symbol(&self, id: SymbolId) -> Option<&symbol_resolver::SymbolStorage> {
+        self.layers.iter().find_map(|layer| match layer {
+            SemanticLayerKind::SymbolResolver(resolver) => resolver.lookup(id),
+            _ => None,
+        })
+    }
+
+    /// The symbols in scope at the offset given to `SymbolResolver::with_scope_query`,
+    /// innermost first.
+    pub fn scope_at_query(&self) -> Option<&[symbol_resolver::SymbolStorage]> {
+        self.layers.iter().find_map(|layer| match layer {
+            SemanticLayerKind::SymbolResolver(resolver) => resolver.scope_at_query(),
+            _ => None,
+        })
+    }
+
+    /// Records a diagnostic that does not stop the analysis.
+    pub(crate) fn warn(&mut self, warning: error::SemanticError) {
+        let level = warning
+            .lint()
+            .and_then(|lint| self.lint_scopes.level(lint, warning.span()));
+        self.warnings.push((warning, level));
+    }
+
+    /// Takes the warnings reported so far, with the level set by the attributes of the
+    /// items they are in.
+    pub fn take_warnings(&mut self) -> Vec<(error::SemanticError, Option<LintLevel>)> {
+        std::mem::take(&mut self.warnings)
+    }
+
     pub fn add_type_checker_layer(&mut self, t: TypeChecker) {
         self.layers.push(SemanticLayerKind::TypeChecker(t));
     }
@@ -0,0 +1,63 @@
+use popper_ast::ast::{LangAst, LangNodeKind, Span};
+use popper_ast::attribute::{Attribute, LintLevel};
+use popper_ast::layer::Ast;
+use popper_error_core::lint;
+
+/// A lint level set by an attribute, applying to the whole item carrying it, or to the
+/// whole module.
+#[derive(Debug, Clone)]
+struct LintScope {
+    span: Span,
+    level: LintLevel,
+    lints: Vec<String>,
+}
+
+/// The lint levels set by the `allow`, `warn` and `deny` attributes of a module.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LintScopes {
+    scopes: Vec<LintScope>,
+}
+
+impl LintScopes {
+    pub(crate) fn from_ast(ast: &LangAst) -> LintScopes {
+        let mut scopes = Vec::new();
+        let module_attrs = ast.module_attrs.iter().flat_map(|module_attrs| &module_attrs.attrs);
+        for attr in module_attrs {
+            if let Attribute::Lint(level, lints) = attr {
+                scopes.push(LintScope {
+                    span: Span::new(0, usize::MAX),
+                    level: *level,
+                    lints: lints.clone(),
+                });
+            }
+        }
+        for id in ast.nodes() {
+            let node = ast.get(id);
+            let LangNodeKind::FunctionDef { attrs, .. } = &node.kind else {
+                continue;
+            };
+            for attr in attrs {
+                if let Attribute::Lint(level, lints) = attr {
+                    scopes.push(LintScope {
+                        span: node.span,
+                        level: *level,
+                        lints: lints.clone(),
+                    });
+                }
+            }
+        }
+        LintScopes { scopes }
+    }
+
+    /// The level of `lint` set by the innermost item around `span`, `None` if no
+    /// attribute mentions it. Among the attributes of one item, the last one wins.
+    pub(crate) fn level(&self, lint: &str, span: Span) -> Option<LintLevel> {
+        self.scopes
+            .iter()
+            .rev()
+            .filter(|scope| scope.span.lo <= span.lo && span.hi <= scope.span.hi)
+            .filter(|scope| scope.lints.iter().any(|name| lint::matches(name, lint)))
+            .min_by_key(|scope| scope.span.hi - scope.span.lo)
+            .map(|scope| scope.level)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{SemanticAnalyzer, SemanticLayerKind};
+use popper_ast::ast::{LangNodeKind, Span, Visibility};
+use popper_ast::file::FileId;
+use popper_ast::type_::Type;
+
+/// A top-level item of an analyzed module, visible to the modules importing it.
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub name: String,
+    pub ty: Type,
+    pub span: Span,
+    pub visibility: Visibility,
+}
+
+impl SemanticAnalyzer {
+    /// Makes a symbol of another module visible from the root scope of this one.
+    /// Private symbols are imported too, so that using them reports `SymbolIsPrivate`
+    /// rather than `SymbolNotFound`. Must be called before `analyze`.
+    pub fn import_symbol(&mut self, export: &Export, file: FileId) {
+        let id = self.ast.add_symbol(&export.name);
+        for layer in &mut self.layers {
+            if let SemanticLayerKind::SymbolResolver(resolver) = layer {
+                resolver.import(id, export.ty.clone(), export.span, export.visibility, file);
+            }
+        }
+    }
+
+    /// Records the public symbols of a module that this one does not import, so that
+    /// using one of them hints at the missing `import <path>;`.
+    pub fn add_unimported_module(&mut self, path: String, exports: &[Export]) {
+        let names = exports
+            .iter()
+            .filter(|export| export.visibility == Visibility::Public)
+            .map(|export| export.name.clone())
+            .collect();
+        self.unimported_modules.push((path, names));
+    }
+
+    /// The import path of a module defining `name` that this one does not import.
+    pub(crate) fn unimported_module(&self, name: &str) -> Option<&str> {
+        self.unimported_modules
+            .iter()
+            .find(|(_, names)| names.iter().any(|n| n == name))
+            .map(|(path, _)| path.as_str())
+    }
+
+    /// Lists the top-level functions and variables of the analyzed module with their
+    /// resolved types. Must be called after `analyze`.
+    pub fn exports(&self) -> Vec<Export> {
+        let LangNodeKind::Block(items) = &self.ast.get(self.ast.root).kind else {
+            return Vec::new();
+        };
+
+        items
+            .iter()
+            .filter_map(|&id| {
+                let node = self.ast.get(id);
+                let (name, visibility) = match &node.kind {
+                    LangNodeKind::FunctionDef {
+                        name, visibility, ..
+                    } => (name, *visibility),
+                    LangNodeKind::Let(let_) => (&let_.name, let_.visibility),
+                    _ => return None,
+                };
+                let ty = self.hir.get_type(id.into())?;
+                Some(Export {
+                    name: self.ast.get_symbol(name.0).name.clone(),
+                    ty: ty.clone(),
+                    span: node.span,
+                    visibility,
+                })
+            })
+            .collect()
+    }
+}
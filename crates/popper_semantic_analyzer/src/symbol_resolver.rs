@@ -1,15 +1,65 @@
-use crate::error::{Result, SemanticError};
+use crate::error::{Result, SemanticError, SymbolNotFound};
+use crate::hir::Definition;
+use crate::{flow, suggest};
 use crate::{LayerOutput, SemanticAnalyzer, SemanticLayer};
-use popper_ast::ast::{Expr, LangAst, LangNodeId, LangNodeKind, Span, SymbolId};
+use popper_ast::ast::{
+    ArgumentParamDef, Expr, Ident, LangAst, LangNodeId, LangNodeKind, Span, SymbolId, Visibility,
+};
+use popper_ast::attribute::Attribute;
+use popper_ast::file::FileId;
 use popper_ast::layer::Layer;
+use popper_ast::token::KEYWORDS;
 use popper_ast::type_::Type;
 
+/// What introduced a symbol, used to word the unused-symbol warnings and to classify
+/// the uses of the symbol for editors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Variable,
+    Parameter,
+    Function,
+    /// A function declared with `func(C)`, implemented outside of Popper.
+    ExternFunction,
+}
+
+impl SymbolKind {
+    /// The kind of the function declared with `attrs`.
+    fn function(attrs: &[Attribute]) -> SymbolKind {
+        if attrs.contains(&Attribute::StdCallC) {
+            SymbolKind::ExternFunction
+        } else {
+            SymbolKind::Function
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SymbolStorage {
     pub id: SymbolId,
     pub ty: Type,
     pub span: Span,
+    /// The span of the name in the definition, `span` for imported symbols.
+    pub name_span: Span,
+    pub kind: SymbolKind,
     pub used_count: usize,
+    pub visibility: Visibility,
+    /// The module the symbol was imported from, `None` if it is defined in the current one.
+    pub file: Option<FileId>,
+}
+
+impl SymbolStorage {
+    pub fn new(id: SymbolId, ty: Type, span: Span, name_span: Span, kind: SymbolKind, visibility: Visibility) -> Self {
+        SymbolStorage {
+            id,
+            ty,
+            span,
+            name_span,
+            kind,
+            used_count: 0,
+            visibility,
+            file: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -46,12 +96,24 @@ impl Scope {
         Scope::new(self.clone())
     }
 
-    pub fn insert(&mut self, id: SymbolId, ty: Type, span: Span) {
+    pub fn insert(&mut self, symbol: SymbolStorage) {
+        self.symbols.push(symbol);
+    }
+
+    pub fn import(&mut self, id: SymbolId, ty: Type, span: Span, visibility: Visibility, file: FileId) {
+        let kind = match ty {
+            Type::Function(..) => SymbolKind::Function,
+            _ => SymbolKind::Variable,
+        };
         self.symbols.push(SymbolStorage {
             id,
             ty,
             span,
+            name_span: span,
+            kind,
             used_count: 0,
+            visibility,
+            file: Some(file),
         });
     }
 
@@ -64,6 +126,27 @@ impl Scope {
         self.parent.as_ref().and_then(|p| p.get(id))
     }
 
+    pub fn lookup(&self, id: SymbolId) -> Option<&SymbolStorage> {
+        self.symbols
+            .iter()
+            .find(|symbol| symbol.id == id)
+            .or_else(|| self.parent.as_ref().and_then(|p| p.lookup(id)))
+    }
+
+    /// Every symbol visible from this scope, innermost first.
+    pub fn visible_symbols(&self) -> Vec<&SymbolStorage> {
+        let mut symbols: Vec<&SymbolStorage> = self.symbols.iter().rev().collect();
+        if let Some(parent) = &self.parent {
+            symbols.extend(parent.visible_symbols());
+        }
+        symbols
+    }
+
+    /// Looks `id` up in this scope only, ignoring the parents.
+    pub fn get_local(&self, id: SymbolId) -> Option<&SymbolStorage> {
+        self.symbols.iter().find(|symbol| symbol.id == id)
+    }
+
     pub fn get_mut(&mut self, id: SymbolId) -> Option<&mut SymbolStorage> {
         for symbol in &mut self.symbols {
             if symbol.id == id {
@@ -91,6 +174,12 @@ pub struct SymbolResolver {
     global_scope: Scope,
     current_scope_idx: usize,
     expected_ret_ty: Option<Type>,
+    /// Top-level functions already inserted by `hoist_functions`.
+    hoisted: Vec<LangNodeId>,
+    /// The byte offset to record the symbols in scope at, see `with_scope_query`.
+    scope_query: Option<usize>,
+    /// The symbols in scope at `scope_query`, innermost first.
+    scope_at_query: Option<Vec<SymbolStorage>>,
 }
 
 impl SymbolResolver {
@@ -99,25 +188,58 @@ impl SymbolResolver {
             global_scope: Scope::new_root(),
             current_scope_idx: 0,
             expected_ret_ty: None,
+            hoisted: Vec::new(),
+            scope_query: None,
+            scope_at_query: None,
+        }
+    }
+
+    /// A resolver that also records the symbols in scope at the byte offset `offset`
+    /// when the analysis reaches it, for editors to complete the names there.
+    pub fn with_scope_query(offset: usize) -> SymbolResolver {
+        SymbolResolver {
+            scope_query: Some(offset),
+            ..SymbolResolver::new()
         }
     }
 
+    /// The symbols in scope at the offset given to `with_scope_query`, innermost first.
+    /// `None` if the analysis stopped before reaching it.
+    pub fn scope_at_query(&self) -> Option<&[SymbolStorage]> {
+        self.scope_at_query.as_deref()
+    }
+
     pub fn enter_scope(&mut self) {
         self.current_scope_idx += 1;
         self.global_scope = self.global_scope.create_child();
     }
 
-    pub fn exit_scope(&mut self) {
-        if self.current_scope_idx > 0 {
-            self.current_scope_idx -= 1;
-            if let Some(parent) = self.global_scope.get_parent(self.current_scope_idx) {
-                self.global_scope = parent.clone();
-            }
+    /// Leaves the current scope, returning the symbols it defined.
+    pub fn exit_scope(&mut self) -> Vec<SymbolStorage> {
+        if self.current_scope_idx == 0 {
+            return Vec::new();
+        }
+        self.current_scope_idx -= 1;
+        let Some(parent) = self.global_scope.get_parent(1).cloned() else {
+            return Vec::new();
+        };
+        std::mem::replace(&mut self.global_scope, parent).symbols
+    }
+
+    /// Leaves the scopes entered since the scope depth was `depth`, when an error stopped
+    /// the analysis of the nodes that entered them.
+    fn exit_scopes_to(&mut self, depth: usize) {
+        while self.current_scope_idx > depth {
+            self.exit_scope();
         }
     }
 
-    pub fn insert(&mut self, id: SymbolId, ty: Type, span: Span) {
-        self.global_scope.insert(id, ty, span);
+    pub fn insert(&mut self, symbol: SymbolStorage) {
+        self.global_scope.insert(symbol);
+    }
+
+    pub fn import(&mut self, id: SymbolId, ty: Type, span: Span, visibility: Visibility, file: FileId) {
+        self.global_scope.import(id, ty, span, visibility, file);
     }
 
     pub fn get(&self, id: SymbolId) -> Option<&Type> {
@@ -127,6 +249,150 @@ impl SymbolResolver {
     pub fn get_mut(&mut self, id: SymbolId) -> Option<&mut SymbolStorage> {
         self.global_scope.get_mut(id)
     }
+
+    pub fn get_local(&self, id: SymbolId) -> Option<&SymbolStorage> {
+        self.global_scope.get_local(id)
+    }
+
+    pub fn lookup(&self, id: SymbolId) -> Option<&SymbolStorage> {
+        self.global_scope.lookup(id)
+    }
+
+    pub fn visible_symbols(&self) -> Vec<&SymbolStorage> {
+        self.global_scope.visible_symbols()
+    }
+}
+
+fn function_type(params: &[ArgumentParamDef], ret: &Type) -> Type {
+    let param_types = params.iter().map(|param| param.ty.clone()).collect();
+    Type::Function(param_types, Box::new(ret.clone()))
+}
+
+/// The span of a function definition up to its body, to point at the signature
+/// rather than at the whole function.
+fn signature_span(ast: &LangAst, span: Span, body: Option<LangNodeId>) -> Span {
+    match body {
+        Some(body) => Span::new(span.lo, ast.get(body).span.lo),
+        None => span,
+    }
+}
+
+impl SymbolResolver {
+    /// Inserts `symbol` in the current scope, reporting `DuplicateDefinition` if the scope
+    /// already defines it. Shadowing a symbol of a parent scope is allowed.
+    fn define(layer_id: usize, analyzer: &mut SemanticAnalyzer, symbol: SymbolStorage) -> Result<()> {
+        let resolver = analyzer.layers[layer_id].symbol_resolver_mut();
+        if let Some(previous) = resolver.get_local(symbol.id) {
+            return Err(SemanticError::duplicate_definition(
+                analyzer.ast.get_symbol(symbol.id).name.clone(),
+                symbol.span,
+                previous.span,
+            ));
+        }
+        resolver.insert(symbol);
+        Ok(())
+    }
+
+    /// Builds the `SymbolNotFound` error for `name`, suggesting the closest visible symbol,
+    /// otherwise mentioning the closest keyword, and the module to import when another one
+    /// defines `name`.
+    fn symbol_not_found(layer_id: usize, analyzer: &mut SemanticAnalyzer, name: Ident, span: Span) -> SemanticError {
+        let name = analyzer.ast.get_symbol(name.0).name.clone();
+        let resolver = analyzer.layers[layer_id].symbol_resolver_mut();
+        let visible: Vec<String> = resolver
+            .visible_symbols()
+            .into_iter()
+            .filter(|symbol| symbol.file.is_none() || symbol.visibility == Visibility::Public)
+            .map(|symbol| analyzer.ast.get_symbol(symbol.id).name.clone())
+            .collect();
+        let similar = suggest::best_match(&name, visible.iter().map(String::as_str)).map(str::to_string);
+        let keyword = match similar {
+            Some(_) => None,
+            None => suggest::best_match(&name, KEYWORDS.iter().copied()).map(str::to_string),
+        };
+        let module = analyzer.unimported_module(&name).map(str::to_string);
+        SemanticError::SymbolNotFound(SymbolNotFound {
+            name,
+            span,
+            similar,
+            keyword,
+            module,
+        })
+    }
+
+    /// Warns about the symbols of an exited scope that were never used. Names starting
+    /// with `_`, public symbols, `func(C)` declarations, whose name is the one of the
+    /// linked symbol, and the `main` function of the root scope are exempt.
+    fn report_unused(analyzer: &mut SemanticAnalyzer, symbols: Vec<SymbolStorage>, is_root: bool) {
+        for symbol in symbols {
+            let name = analyzer.ast.get_symbol(symbol.id).name.clone();
+            if symbol.used_count > 0
+                || symbol.visibility == Visibility::Public
+                || symbol.kind == SymbolKind::ExternFunction
+                || name.starts_with('_')
+                || (is_root && name == "main")
+            {
+                continue;
+            }
+            let warning = match symbol.kind {
+                SymbolKind::Variable => SemanticError::unused_variable(name, symbol.span, Some(symbol.name_span)),
+                SymbolKind::Parameter => SemanticError::unused_parameter(name, symbol.span, Some(symbol.name_span)),
+                SymbolKind::Function | SymbolKind::ExternFunction => {
+                    SemanticError::unused_function(name, symbol.span)
+                }
+            };
+            analyzer.warn(warning);
+        }
+    }
+
+    /// Records the symbols in scope if the query offset is in `within` and not after `end`.
+    /// A nested scope is analyzed after the scope around it, so the innermost scope
+    /// around the offset records last. Returns whether the symbols were recorded.
+    fn query_scope(layer_id: usize, analyzer: &mut SemanticAnalyzer, within: Span, end: Option<usize>) -> bool {
+        let resolver = analyzer.layers[layer_id].symbol_resolver_mut();
+        let Some(offset) = resolver.scope_query else {
+            return false;
+        };
+        if offset < within.lo || offset > within.hi || end.is_some_and(|end| offset > end) {
+            return false;
+        }
+        resolver.scope_at_query = Some(resolver.visible_symbols().into_iter().cloned().collect());
+        true
+    }
+
+    /// Inserts the signature of every function in `items` before any body is analyzed,
+    /// so that functions can be called before their definition and recurse mutually.
+    fn hoist_functions(
+        layer_id: usize,
+        analyzer: &mut SemanticAnalyzer,
+        items: &[LangNodeId],
+    ) -> Result<()> {
+        for &item in items {
+            let node = analyzer.ast.get(item).clone();
+            if let LangNodeKind::FunctionDef {
+                name,
+                name_span,
+                visibility,
+                attrs,
+                params,
+                ret,
+                body,
+                ..
+            } = node.kind
+            {
+                let ty = function_type(&params, &ret);
+                let span = signature_span(&analyzer.ast, node.span, body);
+                let kind = SymbolKind::function(&attrs);
+                let symbol = SymbolStorage::new(name.0, ty, span, name_span, kind, visibility);
+                Self::define(layer_id, analyzer, symbol)?;
+                analyzer.layers[layer_id]
+                    .symbol_resolver_mut()
+                    .hoisted
+                    .push(item);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Iterator for SymbolResolver {
@@ -156,63 +422,134 @@ impl SemanticLayer for SymbolResolver {
                     .symbol_resolver_mut()
                     .get_mut(id.0)
                 {
+                    if let (Some(file), Visibility::Private) = (ss.file, ss.visibility) {
+                        let symbol = analyzer.ast.get_symbol(id.0);
+                        return LayerOutput::ResErr(SemanticError::symbol_is_private(
+                            symbol.name.clone(),
+                            lang_node.span,
+                            file,
+                            ss.span,
+                        ));
+                    }
                     analyzer.hir.incr_used(node.into());
                     analyzer.hir.set_type(node.into(), ss.ty.clone());
+                    let definition = Definition {
+                        file: ss.file,
+                        span: ss.span,
+                        kind: ss.kind,
+                    };
+                    analyzer.hir.set_definition(node.into(), definition);
                     ss.used_count += 1; // Increment usage count
                     LayerOutput::ResOk(ss.ty.clone())
                 } else {
-                    LayerOutput::ResErr({
-                        let symbol = analyzer.ast.get_symbol(id.0);
-                        SemanticError::symbol_not_found(symbol.name.clone(), lang_node.span)
-                    })
+                    LayerOutput::ResErr(Self::symbol_not_found(
+                        layer_id,
+                        analyzer,
+                        id,
+                        lang_node.span,
+                    ))
                 }
             }
             LangNodeKind::Let(l) => {
-                let ty = analyzer.analyze(l.value)?.unwrap();
-                analyzer.layers[layer_id].symbol_resolver_mut().insert(
+                let value = analyzer.analyze(l.value);
+                let querying = analyzer.layers[layer_id].symbol_resolver_mut().scope_query.is_some();
+                let (ty, error) = match value {
+                    Ok(ty) => (ty.unwrap(), None),
+                    // When the scope is queried, the analysis goes on after the error, so the
+                    // name is still defined to be offered later on. Its type is unknown,
+                    // `void` stands for it.
+                    Err(err) if querying => (Type::Void, Some(err)),
+                    Err(err) => return LayerOutput::ResErr(err),
+                };
+                let symbol = SymbolStorage::new(
                     l.name.0,
                     ty.clone(),
                     lang_node.span,
+                    l.name_span,
+                    SymbolKind::Variable,
+                    l.visibility,
                 );
+                Self::define(layer_id, analyzer, symbol)?;
+                if let Some(err) = error {
+                    return LayerOutput::ResErr(err);
+                }
                 analyzer.hir.set_type(node.into(), ty.clone());
                 LayerOutput::ResOk(ty)
             }
             LangNodeKind::FunctionDef {
                 name,
+                name_span,
+                visibility,
+                attrs,
                 params,
                 ret,
                 body,
-                ..
+                is_expr,
             } => {
-                let mut param_types = Vec::new();
-                for param in params.clone() {
-                    param_types.push(param.ty.clone());
-                }
+                let ty = function_type(&params, &ret);
 
-                let ty = Type::Function(param_types, Box::new(ret.clone()));
-
-                analyzer.layers[layer_id].symbol_resolver_mut().insert(
-                    name.0,
-                    ty.clone(),
-                    lang_node.span,
-                );
-                analyzer.hir.set_type(node.into(), ty.clone());
-                analyzer.layers[layer_id]
+                if !analyzer.layers[layer_id]
                     .symbol_resolver_mut()
-                    .expected_ret_ty = Some(ret.clone());
+                    .hoisted
+                    .contains(&node)
+                {
+                    let span = signature_span(&analyzer.ast, lang_node.span, body);
+                    let symbol = SymbolStorage::new(
+                        name.0,
+                        ty.clone(),
+                        span,
+                        name_span,
+                        SymbolKind::function(&attrs),
+                        visibility,
+                    );
+                    Self::define(layer_id, analyzer, symbol)?;
+                }
+                analyzer.hir.set_type(node.into(), ty.clone());
                 if let Some(body) = body {
+                    let enclosing_ret_ty = analyzer.layers[layer_id]
+                        .symbol_resolver_mut()
+                        .expected_ret_ty
+                        .replace(ret.clone());
                     analyzer.layers[layer_id]
                         .symbol_resolver_mut()
                         .enter_scope();
                     for param in params.iter() {
-                        analyzer.layers[layer_id].symbol_resolver_mut().insert(
+                        let symbol = SymbolStorage::new(
                             param.name.0,
                             param.ty.clone(),
-                            lang_node.span,
+                            param.span,
+                            param.name_span,
+                            SymbolKind::Parameter,
+                            Visibility::Private,
                         );
+                        Self::define(layer_id, analyzer, symbol)?;
+                    }
+                    if is_expr {
+                        Self::query_scope(layer_id, analyzer, lang_node.span, None);
                     }
                     analyzer.analyze(body)?;
-                    analyzer.layers[layer_id].symbol_resolver_mut().exit_scope();
+                    let resolver = analyzer.layers[layer_id].symbol_resolver_mut();
+                    let params = resolver.exit_scope();
+                    resolver.expected_ret_ty = enclosing_ret_ty;
+                    Self::report_unused(analyzer, params, false);
+
+                    if !is_expr {
+                        let flow = flow::analyze(&analyzer.ast, body);
+                        for unreachable in flow.unreachable {
+                            analyzer.warn(SemanticError::unreachable_code(
+                                unreachable.span,
+                                unreachable.cause,
+                            ));
+                        }
+                        if flow.returns.is_none() && ret != Type::Void {
+                            let end = analyzer.ast.get(body).span.hi;
+                            return LayerOutput::ResErr(SemanticError::missing_return(
+                                analyzer.ast.get_symbol(name.0).name.clone(),
+                                ret,
+                                Span::new(end.saturating_sub(1), end),
+                            ));
+                        }
+                    }
                 }
 
                 LayerOutput::ResOk(ty)
@@ -231,9 +568,6 @@ impl SemanticLayer for SymbolResolver {
                             lang_node.span,
                         ));
                     }
-                    analyzer.layers[layer_id]
-                        .symbol_resolver_mut()
-                        .expected_ret_ty = None; // Reset after return
                 } else {
                     return LayerOutput::ResErr(SemanticError::return_not_in_function(
                         lang_node.span,
@@ -241,14 +575,54 @@ impl SemanticLayer for SymbolResolver {
                 }
                 LayerOutput::Handled
             }
+            LangNodeKind::Import(_) => LayerOutput::Handled,
             LangNodeKind::Block(elts) => {
                 analyzer.layers[layer_id]
                     .symbol_resolver_mut()
                     .enter_scope();
+                if node == analyzer.ast.root {
+                    Self::hoist_functions(layer_id, analyzer, &elts)?;
+                }
+                // The root block holds the whole file, including the blanks after its
+                // last item.
+                let within = if node == analyzer.ast.root {
+                    Span::new(0, usize::MAX)
+                } else {
+                    lang_node.span
+                };
+                let mut queried = false;
+                let mut first_error = None;
                 for elt in elts {
-                    analyzer.analyze(elt)?;
+                    if !queried {
+                        let end = analyzer.ast.get(elt).span.hi;
+                        queried = Self::query_scope(layer_id, analyzer, within, Some(end));
+                    }
+                    let resolver = analyzer.layers[layer_id].symbol_resolver_mut();
+                    let (depth, ret_ty) = (resolver.current_scope_idx, resolver.expected_ret_ty.clone());
+                    if let Err(err) = analyzer.analyze(elt) {
+                        // An editor queries the scope while the file is being edited, often
+                        // with an error above the cursor: keep going to reach the query
+                        // offset, and report the first error once there.
+                        let resolver = analyzer.layers[layer_id].symbol_resolver_mut();
+                        let query_ahead = resolver
+                            .scope_query
+                            .is_some_and(|offset| within.lo <= offset && offset <= within.hi);
+                        if queried || !query_ahead {
+                            return LayerOutput::ResErr(err);
+                        }
+                        resolver.exit_scopes_to(depth);
+                        resolver.expected_ret_ty = ret_ty;
+                        first_error.get_or_insert(err);
+                    }
+                }
+                if !queried {
+                    Self::query_scope(layer_id, analyzer, within, None);
+                }
+                let symbols = analyzer.layers[layer_id].symbol_resolver_mut().exit_scope();
+                if let Some(err) = first_error {
+                    return LayerOutput::ResErr(err);
                 }
-                analyzer.layers[layer_id].symbol_resolver_mut().exit_scope();
+                Self::report_unused(analyzer, symbols, node == analyzer.ast.root);
                 LayerOutput::Handled
             }
             _ => LayerOutput::NotHandled,
@@ -1,7 +1,8 @@
+use crate::cast::cast_kind;
 use crate::error::SemanticError;
 use crate::{LayerOutput, SemanticAnalyzer, SemanticLayer};
-use popper_ast::ast::{Expr, LangAst, LangNodeId, LangNodeKind};
-use popper_ast::layer::Layer;
+use popper_ast::ast::{Expr, LangAst, LangNodeId, LangNodeKind, Span};
+use popper_ast::layer::{Ast, Layer};
 use popper_ast::type_::Type;
 
 #[derive(Default, Debug, Clone)]
@@ -10,6 +11,31 @@ impl TypeChecker {
     pub fn new() -> Self {
         TypeChecker {}
     }
+
+    /// The declaration of the `index`-th parameter of the function called through
+    /// `function`, when it is a function of the current module called by name.
+    fn parameter_span(analyzer: &SemanticAnalyzer, function: LangNodeId, index: usize) -> Option<Span> {
+        let LangNodeKind::Expr(Expr::Ident(name)) = analyzer.ast.get(function).kind else {
+            return None;
+        };
+        let symbol = analyzer.symbol(name.0)?;
+        if symbol.file.is_some() {
+            return None;
+        }
+        analyzer.ast.nodes().find_map(|id| {
+            let node = analyzer.ast.get(id);
+            match &node.kind {
+                LangNodeKind::FunctionDef {
+                    name: def_name,
+                    params,
+                    ..
+                } if *def_name == name && node.span.lo == symbol.span.lo => {
+                    params.get(index).map(|param| param.span)
+                }
+                _ => None,
+            }
+        })
+    }
 }
 
 impl SemanticLayer for TypeChecker {
@@ -35,20 +61,15 @@ impl SemanticLayer for TypeChecker {
                         node.span,
                     ));
                 }
-                analyzer.analyze(then_branch)?.unwrap();
+                analyzer.analyze(then_branch)?;
                 if let Some(else_branch) = else_branch {
-                    analyzer.analyze(else_branch)?.unwrap();
-                }
-                LayerOutput::Handled
-            }
-            LangNodeKind::Block(block) => {
-                for stmt in block {
-                    analyzer.analyze(stmt)?.unwrap();
+                    analyzer.analyze(else_branch)?;
                 }
                 LayerOutput::Handled
             }
             LangNodeKind::Expr(Expr::Int(_)) => LayerOutput::ResOk(Type::Int),
             LangNodeKind::Expr(Expr::String(_)) => LayerOutput::ResOk(Type::String),
+            LangNodeKind::Expr(Expr::Char(_)) => LayerOutput::ResOk(Type::Char),
             LangNodeKind::Expr(Expr::UnaryOp(_, v)) => {
                 let ty = analyzer.analyze(v)?.unwrap();
                 match ty {
@@ -75,6 +96,16 @@ impl SemanticLayer for TypeChecker {
                     ))
                 }
             }
+            LangNodeKind::Expr(Expr::Cast(value, ty)) => {
+                let value_ty = analyzer.analyze(value)?.unwrap();
+                match cast_kind(&value_ty, &ty) {
+                    Some(kind) => {
+                        analyzer.hir.set_cast_kind(node_id.into(), kind);
+                        LayerOutput::ResOk(ty)
+                    }
+                    None => LayerOutput::ResErr(SemanticError::invalid_cast(value_ty, ty, node.span)),
+                }
+            }
             LangNodeKind::FunctionCall { function, args } => {
                 let func_ty = analyzer.analyze(function)?.unwrap();
                 if let Type::Function(param_types, ret_type) = func_ty {
@@ -85,14 +116,15 @@ impl SemanticLayer for TypeChecker {
                             node.span,
                         ));
                     }
-                    for (arg, param_ty) in args.iter().zip(param_types) {
+                    for (index, (arg, param_ty)) in args.iter().zip(param_types).enumerate() {
                         let arg_ty = analyzer.analyze(*arg)?.unwrap();
                         if arg_ty != param_ty {
                             let node_arg = analyzer.ast.get(*arg);
-                            return LayerOutput::ResErr(SemanticError::type_mismatch(
+                            return LayerOutput::ResErr(SemanticError::argument_type_mismatch(
                                 param_ty.to_string(),
                                 arg_ty.to_string(),
                                 node_arg.span,
+                                Self::parameter_span(analyzer, function, index),
                             ));
                         }
                     }
@@ -0,0 +1,49 @@
+mod common;
+
+use common::analyze;
+use popper_ast::ast::{Expr, LangNodeKind};
+use popper_ast::layer::Ast;
+use popper_semantic_analyzer::cast::CastKind;
+
+#[test]
+fn test_cast_kind_recorded() {
+    let analysis = analyze("let x = 1 as float;\nlet y = x as int;\nlet z = y as int;");
+    assert!(analysis.error.is_none());
+
+    let ast = analysis.analyzer.ast();
+    let kinds: Vec<Option<CastKind>> = ast
+        .nodes()
+        .filter(|&id| matches!(ast.get(id).kind, LangNodeKind::Expr(Expr::Cast(..))))
+        .map(|id| analysis.analyzer.hir().get_cast_kind(id.into()))
+        .collect();
+    assert_eq!(
+        kinds,
+        [Some(CastKind::IntToFloat), Some(CastKind::FloatToInt), Some(CastKind::NoOp)]
+    );
+}
+
+#[test]
+fn test_invalid_cast() {
+    let source = "let s = \"a\" as int;";
+    let error = analyze(source).error.unwrap();
+    assert_eq!(error.code(), 1006);
+    assert_eq!(error.message(), "Invalid cast from `string` to `int`");
+    assert_eq!(
+        error.note().unwrap(),
+        "Only casts between `int` and `float`, between `int` and `char`, and from `bool` to `int` are allowed"
+    );
+    assert_eq!(&source[error.span().lo..error.span().hi], "\"a\" as int");
+}
+
+#[test]
+fn test_cast_binds_tighter_than_add() {
+    // `1 + (2 as float)`, adding an `int` to a `float`.
+    assert_eq!(analyze("let x = 1 + 2 as float;").error_code(), Some(1002));
+    assert!(analyze("func f(x: float) -> int = 1 + x as int").error.is_none());
+}
+
+#[test]
+fn test_char_casts() {
+    assert!(analyze("let n = 'a' as int;\nlet c = 65 as char;").error.is_none());
+    assert_eq!(analyze("let f = 'a' as float;").error_code(), Some(1006));
+}
@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+
+use popper_ast::file::FileId;
+use popper_error_core::Diagnostics;
+use popper_index::Idx;
+use popper_parser::Parser;
+use popper_semantic_analyzer::symbol_resolver::SymbolResolver;
+use popper_semantic_analyzer::type_checker::TypeChecker;
+use popper_semantic_analyzer::SemanticAnalyzer;
+
+/// The outcome of the analysis of a single module.
+pub struct Analysis {
+    pub analyzer: SemanticAnalyzer,
+    pub error: Option<Box<dyn Diagnostics>>,
+    pub warnings: Vec<Box<dyn Diagnostics>>,
+}
+
+impl Analysis {
+    pub fn error_code(&self) -> Option<u32> {
+        self.error.as_ref().map(|error| error.code())
+    }
+
+    pub fn warning_messages(&self) -> Vec<String> {
+        self.warnings.iter().map(|warning| warning.message()).collect()
+    }
+}
+
+/// The file the module given to `analyze_importing` is imported from.
+pub const IMPORTED_FILE: FileId = FileId::MAX;
+
+/// Parses `source` and runs the type checker and symbol resolver over it.
+pub fn analyze(source: &str) -> Analysis {
+    analyze_module(source, None)
+}
+
+/// Like `analyze`, with `source` importing the module `imported`, analyzed first.
+pub fn analyze_importing(source: &str, imported: &str) -> Analysis {
+    let imported = analyze(imported);
+    assert!(imported.error.is_none());
+    analyze_module(source, Some(&imported.analyzer))
+}
+
+fn analyze_module(source: &str, imported: Option<&SemanticAnalyzer>) -> Analysis {
+    let ast = Parser::new(source).parse().unwrap();
+    let mut analyzer = SemanticAnalyzer::new(ast.clone());
+    analyzer.add_type_checker_layer(TypeChecker::new());
+    analyzer.add_symbol_resolver_layer(SymbolResolver::new());
+    for export in imported.map(SemanticAnalyzer::exports).unwrap_or_default() {
+        analyzer.import_symbol(&export, IMPORTED_FILE);
+    }
+    let error = analyzer
+        .analyze(ast.root)
+        .err()
+        .map(|error| Box::new(error) as Box<dyn Diagnostics>);
+    let warnings = analyzer
+        .take_warnings()
+        .into_iter()
+        .map(|(warning, _)| Box::new(warning) as Box<dyn Diagnostics>)
+        .collect();
+    Analysis {
+        analyzer,
+        error,
+        warnings,
+    }
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::analyze;
+
+/// The duplicate definition in `source`, and the previous one.
+fn duplicate(source: &str) -> (String, &str, &str) {
+    let error = analyze(source).error.unwrap();
+    assert_eq!(error.code(), 1008);
+    let previous = error.secondary_labels()[0].span;
+    (
+        error.message(),
+        &source[error.span().lo..error.span().hi],
+        &source[previous.lo..previous.hi],
+    )
+}
+
+#[test]
+fn test_duplicate_function() {
+    let source = "func main() {}\nfunc main() {}\n";
+    let error = analyze(source).error.unwrap();
+    assert_eq!(error.code(), 1008);
+    assert_eq!(error.message(), "`main` is defined multiple times");
+    assert_eq!(error.span().lo, source.rfind("func main()").unwrap());
+    assert_eq!(error.secondary_labels()[0].span.lo, 0);
+}
+
+#[test]
+fn test_duplicate_parameter() {
+    let source = "pub func f(a: int, a: int) = a";
+    let error = analyze(source).error.unwrap();
+    assert_eq!(error.code(), 1008);
+    assert_eq!(error.span().lo, source.rfind("a: int").unwrap());
+    assert_eq!(error.secondary_labels()[0].span.lo, source.find("a: int").unwrap());
+}
+
+#[test]
+fn test_duplicate_let() {
+    let (message, span, previous) = duplicate("func main() {\n    let x = 1;\n    let x = 2;\n}\n");
+    assert_eq!(message, "`x` is defined multiple times");
+    assert_eq!(span, "let x = 2;");
+    assert_eq!(previous, "let x = 1;");
+
+    // Functions are defined before the other items of the scope.
+    let (_, span, previous) = duplicate("let x = 1;\nfunc x() {}\n");
+    assert_eq!(span, "let x = 1;");
+    assert!(previous.starts_with("func x()"));
+}
+
+#[test]
+fn test_shadowing_in_nested_scope() {
+    let source = "func(C) ready() -> bool;\n\
+                  pub let x = 1;\n\
+                  pub func f(x: int) -> int {\n    if ready() {\n        let x = 2;\n        return x;\n    }\n    return x;\n}\n";
+    assert!(analyze(source).error.is_none());
+}
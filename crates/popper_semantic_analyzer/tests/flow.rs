@@ -0,0 +1,55 @@
+mod common;
+
+use common::analyze;
+use popper_error_core::Severity;
+
+const READY: &str = "func(C) ready() -> bool;\n";
+
+#[test]
+fn test_missing_return() {
+    let source = format!("{READY}pub func f() -> int {{\n    if ready() {{\n        return 1;\n    }}\n}}\n");
+    let error = analyze(&source).error.unwrap();
+    assert_eq!(error.code(), 1009);
+    assert_eq!(error.message(), "Not all paths of `f` return a value");
+    // Points at the closing brace of the body.
+    assert_eq!(error.span().lo, source.rfind('}').unwrap());
+
+    let source = format!("{READY}pub func f() -> int {{}}\n");
+    assert_eq!(analyze(&source).error_code(), Some(1009));
+    assert!(analyze("pub func f() {}").error.is_none());
+}
+
+#[test]
+fn test_all_branches_return() {
+    let source = format!(
+        "{READY}pub func f() -> int {{\n    if ready() {{\n        return 1;\n    }} else {{\n        return 2;\n    }}\n}}\n"
+    );
+    let analysis = analyze(&source);
+    assert!(analysis.error.is_none());
+    assert!(analysis.warnings.is_empty());
+}
+
+#[test]
+fn test_second_return() {
+    let source = format!("{READY}pub func f() -> int {{\n    if ready() {{\n        return 1;\n    }}\n    return 2;\n}}\n");
+    let analysis = analyze(&source);
+    assert!(analysis.error.is_none());
+
+    assert_eq!(analyze("return 1;").error_code(), Some(1005));
+}
+
+#[test]
+fn test_unreachable_code() {
+    let source = "pub func f() -> int {\n    return 1;\n    return 2;\n}\n";
+    let analysis = analyze(source);
+    assert!(analysis.error.is_none());
+    assert_eq!(analysis.warnings.len(), 1);
+
+    let warning = &analysis.warnings[0];
+    assert_eq!(warning.code(), 1010);
+    assert_eq!(warning.severity(), Severity::Warning);
+    assert_eq!(warning.lint(), Some("unreachable_code"));
+    assert_eq!(&source[warning.span().lo..warning.span().hi], "return 2;");
+    let cause = warning.secondary_labels()[0].span;
+    assert_eq!(&source[cause.lo..cause.hi], "return 1;");
+}
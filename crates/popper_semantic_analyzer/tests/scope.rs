@@ -0,0 +1,33 @@
+mod common;
+
+use common::analyze;
+
+#[test]
+fn test_forward_call() {
+    let analysis = analyze("func main() {\n    helper(1);\n}\nfunc helper(_n: int) {}\n");
+    assert!(analysis.error.is_none());
+    assert!(analysis.warnings.is_empty());
+}
+
+#[test]
+fn test_mutual_recursion() {
+    let source = "func(C) done(n: int) -> bool;\n\
+                  pub func even(n: int) -> int {\n    if done(n) {\n        return n;\n    }\n    return odd(n + -1);\n}\n\
+                  func odd(n: int) -> int {\n    return even(n + -1);\n}\n";
+    let analysis = analyze(source);
+    assert!(analysis.error.is_none());
+    assert!(analysis.warnings.is_empty());
+}
+
+#[test]
+fn test_block_scope() {
+    let source = "func(C) ready() -> bool;\n\
+                  func main() {\n    if ready() {\n        let inner = 1;\n    }\n    let outer = inner;\n}\n";
+    let error = analyze(source).error.unwrap();
+    assert_eq!(error.code(), 1001);
+    assert_eq!(error.message(), "Symbol `inner` not found");
+    assert_eq!(&source[error.span().lo..error.span().hi], "inner");
+
+    let error = analyze("func f() {\n    let local = 1;\n}\nlet x = local;").error.unwrap();
+    assert_eq!(error.code(), 1001);
+}
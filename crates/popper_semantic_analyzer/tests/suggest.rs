@@ -0,0 +1,68 @@
+mod common;
+
+use common::analyze;
+use popper_semantic_analyzer::suggest::{best_match, edit_distance};
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("count", "count"), 0);
+    // A transposition of two adjacent characters is a single edit.
+    assert_eq!(edit_distance("ab", "ba"), 1);
+    assert_eq!(edit_distance("flaot", "float"), 1);
+    // Distances are counted in characters, not bytes.
+    assert_eq!(edit_distance("é", "e"), 1);
+}
+
+#[test]
+fn test_best_match() {
+    // At most a third of the length of the name, and at least one edit.
+    assert_eq!(best_match("a", ["b"]), Some("b"));
+    assert_eq!(best_match("ab", ["xy"]), None);
+    assert_eq!(best_match("abcdef", ["abcxyf"]), Some("abcxyf"));
+    assert_eq!(best_match("abcdef", ["axyzef"]), None);
+
+    // The name itself is not a match, a name differing only by case always is.
+    assert_eq!(best_match("x", ["x"]), None);
+    assert_eq!(best_match("COUNT", ["count"]), Some("count"));
+    assert_eq!(best_match("Count", ["counts", "count"]), Some("count"));
+
+    // The closest candidate wins, and ties go to the first one.
+    assert_eq!(best_match("value", ["vaule2", "valeu"]), Some("valeu"));
+    assert_eq!(best_match("cat", ["bat", "hat"]), Some("bat"));
+    assert_eq!(best_match("cat", ["hat", "bat"]), Some("hat"));
+}
+
+#[test]
+fn test_similar_symbol() {
+    let source = "func main() {\n    let value = 1;\n    let _x = valeu;\n}\n";
+    let analysis = analyze(source);
+    let error = analysis.error.unwrap();
+    assert_eq!(error.code(), 1001);
+    let suggestions = error.suggestions();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].replacement, "value");
+
+    // The fixed program is valid.
+    let mut fixed = source.to_string();
+    fixed.replace_range(suggestions[0].span.lo..suggestions[0].span.hi, &suggestions[0].replacement);
+    assert_eq!(fixed, "func main() {\n    let value = 1;\n    let _x = value;\n}\n");
+    assert!(analyze(&fixed).error.is_none());
+}
+
+#[test]
+fn test_similar_keyword() {
+    // Replacing the name with a keyword would not parse, so it is only mentioned.
+    let analysis = analyze("func main() {\n    let _x = flaot;\n}\n");
+    let error = analysis.error.unwrap();
+    assert_eq!(error.code(), 1001);
+    assert!(error.suggestions().is_empty());
+    assert_eq!(error.help().unwrap(), "`float` is a keyword with a similar name");
+
+    // A visible symbol is preferred over a keyword.
+    let analysis = analyze("func main() {\n    let flat = 1;\n    let _x = flaot;\n}\n");
+    let error = analysis.error.unwrap();
+    assert_eq!(error.suggestions()[0].replacement, "flat");
+    assert_eq!(error.help(), None);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::analyze;
+use popper_error_core::Severity;
+
+#[test]
+fn test_unused_warnings() {
+    let source = "func helper(n: int) {\n    let x = 1;\n}\n\nfunc main() {}\n";
+    let analysis = analyze(source);
+    assert!(analysis.error.is_none());
+    assert_eq!(
+        analysis.warning_messages(),
+        ["Unused variable `x`", "Unused parameter `n`", "Unused function `helper`"]
+    );
+    assert!(analysis.warnings.iter().all(|warning| warning.severity() == Severity::Warning));
+
+    let codes: Vec<u32> = analysis.warnings.iter().map(|warning| warning.code()).collect();
+    assert_eq!(codes, [1011, 1012, 1013]);
+    let lints: Vec<Option<&str>> = analysis.warnings.iter().map(|warning| warning.lint()).collect();
+    assert_eq!(
+        lints,
+        [Some("unused_variables"), Some("unused_parameters"), Some("unused_functions")]
+    );
+
+    // Variables and parameters can be prefixed with an underscore in place.
+    let suggestion = &analysis.warnings[0].suggestions()[0];
+    assert_eq!(&source[suggestion.span.lo..suggestion.span.hi], "x");
+    assert_eq!(suggestion.replacement, "_x");
+    let suggestion = &analysis.warnings[1].suggestions()[0];
+    assert_eq!(&source[suggestion.span.lo..suggestion.span.hi], "n");
+    assert_eq!(suggestion.replacement, "_n");
+}
+
+#[test]
+fn test_underscore_suppresses() {
+    let analysis = analyze("func _helper(_n: int) {\n    let _x = 1;\n}\n");
+    assert!(analysis.error.is_none());
+    assert!(analysis.warnings.is_empty());
+}
+
+#[test]
+fn test_exempt_functions() {
+    // `main`, public functions and C declarations are used from outside the module.
+    let analysis = analyze("func main() {}\npub func api() {}\nfunc(C) puts(s: string) -> int;\npub let x = 1;\n");
+    assert!(analysis.error.is_none());
+    assert!(analysis.warnings.is_empty());
+
+    // Only the `main` of the root scope is exempt.
+    let analysis = analyze("pub func f() {\n    func main() {}\n}\n");
+    assert_eq!(analysis.warning_messages(), ["Unused function `main`"]);
+}
+
+#[test]
+fn test_used_symbols() {
+    let analysis = analyze("func helper(n: int) -> int {\n    let x = n;\n    return x;\n}\npub let y = helper(1);\n");
+    assert!(analysis.error.is_none());
+    assert!(analysis.warnings.is_empty());
+}
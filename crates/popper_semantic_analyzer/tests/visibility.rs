@@ -0,0 +1,41 @@
+mod common;
+
+use common::{analyze_importing, IMPORTED_FILE};
+use popper_error_core::Diagnostics;
+use popper_parser::Parser;
+
+const MODULE: &str = "pub func visible() -> int = 1\nfunc hidden() -> int = 2\nlet x = \"private\";\n";
+
+#[test]
+fn test_private_symbol() {
+    assert!(analyze_importing("let a = visible();", MODULE).error.is_none());
+
+    let source = "let a = hidden();";
+    let error = analyze_importing(source, MODULE).error.unwrap();
+    assert_eq!(error.code(), 1007);
+    assert_eq!(error.message(), "Symbol `hidden` is private");
+    assert_eq!(&source[error.span().lo..error.span().hi], "hidden");
+    // The note points at the definition, in the other module.
+    let labels = error.secondary_labels();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].file, Some(IMPORTED_FILE));
+    assert!(MODULE[labels[0].span.lo..].starts_with("func hidden()"));
+}
+
+#[test]
+fn test_visibility_not_allowed() {
+    assert!(Parser::new("pub func f() {}\npub let x = 1;").parse().is_ok());
+
+    let source = "func f() {\n    pub let x = 1;\n}";
+    let error = Parser::new(source).parse().unwrap_err();
+    assert_eq!(error.code(), 104);
+    assert_eq!(&source[error.span().lo..error.span().hi], "pub");
+}
+
+#[test]
+fn test_local_shadows_import() {
+    // Using the imported `x` would report it as private.
+    let source = "let x = 1;\npub let y = x + 1;";
+    assert!(analyze_importing(source, MODULE).error.is_none());
+    assert!(analyze_importing("pub let y = x;", MODULE).error.is_some());
+}
@@ -1,44 +1,224 @@
-use popper_ast::ast::{LangNodeId, LineInfo};
-use popper_ast::file::SourceFileInfo;
-use popper_ast::layer::Ast;
 use popper_context::Context;
-use popper_error_core::{Diagnostics, Error, ErrorInfo};
-use popper_parser::error::ParserError;
-use popper_parser::Parser;
-use popper_semantic_analyzer::SemanticAnalyzerLayer;
+use popper_driver::diff::unified_diff;
+use popper_driver::fix::fix_program;
+use popper_driver::fmt::format_file;
+use popper_driver::{check, load_modules};
+use popper_error_core::lint::{self, LintLevel, LintLevels};
+use popper_error_core::{registry, ErrorFormat, ErrorTable};
 
-fn main() {
-    let mut context = Context::new();
-    let source_file_info = SourceFileInfo::from_file("hello.pop").unwrap();
-    let file = context
-        .file_table_mut()
-        .insert(source_file_info.clone(), None);
-    let mut parser = Parser::from_source_file(source_file_info);
-    match parser.parse() {
-        Ok(ast) => {
-            let res = ast.apply_layer(&mut SemanticAnalyzerLayer);
-            match res {
-                Ok(_) => {
-                    println!("Check successfully!");
-                    // Here you can do something with the AST, like printing it
-                    // println!("{:#?}", ast);
-                }
-                Err(e) => {
-                    let line_info = LineInfo::from_span(e.span(), 1);
-                    let err = Error::new(ErrorInfo::new(line_info, file), e);
+/// What to do with the diagnostics of the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    /// Reports them.
+    Check,
+    /// Applies their suggestions to the source files, or prints the changes as a diff.
+    Fix { dry_run: bool },
+    /// Rewrites the input file in the canonical style, or only prints the changes as a
+    /// diff and fails if there are some.
+    Fmt { check: bool },
+}
+
+/// What `--emit` outputs instead of checking the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emit {
+    /// The AST of the input file as JSON, nodes referring to each other by index.
+    AstJson,
+}
+
+impl Emit {
+    fn from_name(name: &str) -> Option<Emit> {
+        match name {
+            "ast-json" => Some(Emit::AstJson),
+            _ => None,
+        }
+    }
+}
+
+struct Options {
+    command: Command,
+    input: String,
+    lints: LintLevels,
+    error_format: ErrorFormat,
+    explain: Option<String>,
+    emit: Option<Emit>,
+}
 
-                    err.print(context).unwrap();
+/// Parses `[fix [--dry-run] | fmt [--check]] [-W <lint>] [-A <lint>] [-D <lint>]
+/// [--deny-warnings] [--error-format=<human|json|sarif>] [--explain <code>]
+/// [--emit ast-json] [file]`. Lint flags are applied in order, so a later flag overrides
+/// an earlier one.
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut args = args.peekable();
+    let mut command = Command::Check;
+    if args.next_if(|arg| arg == "fix").is_some() {
+        command = Command::Fix { dry_run: false };
+    } else if args.next_if(|arg| arg == "fmt").is_some() {
+        command = Command::Fmt { check: false };
+    }
+    let mut input = None;
+    let mut lints = LintLevels::new();
+    let mut error_format = ErrorFormat::default();
+    let mut explain = None;
+    let mut emit = None;
+
+    while let Some(arg) = args.next() {
+        let level = match arg.as_str() {
+            "-W" => LintLevel::Warn,
+            "-A" => LintLevel::Allow,
+            "-D" => LintLevel::Deny,
+            "--explain" => {
+                explain = Some(args.next().ok_or("`--explain` expects an error code")?);
+                continue;
+            }
+            "--emit" => {
+                let name = args.next().ok_or("`--emit` expects an output kind")?;
+                emit = Some(Emit::from_name(&name).ok_or_else(|| format!("unknown output kind `{name}`"))?);
+                continue;
+            }
+            "--dry-run" if matches!(command, Command::Fix { .. }) => {
+                command = Command::Fix { dry_run: true };
+                continue;
+            }
+            "--check" if matches!(command, Command::Fmt { .. }) => {
+                command = Command::Fmt { check: true };
+                continue;
+            }
+            "--deny-warnings" => {
+                lints.set("warnings", LintLevel::Deny);
+                continue;
+            }
+            flag if flag.starts_with("--error-format=") => {
+                let name = &flag["--error-format=".len()..];
+                error_format = ErrorFormat::from_name(name)
+                    .ok_or_else(|| format!("unknown error format `{name}`"))?;
+                continue;
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            _ => {
+                if input.replace(arg).is_some() {
+                    return Err("only one input file can be given".to_string());
                 }
+                continue;
             }
+        };
+        let name = args
+            .next()
+            .ok_or_else(|| format!("`{arg}` expects a lint name"))?;
+        if !lint::is_known(&name) {
+            return Err(format!("unknown lint `{name}`"));
         }
-        Err(ParserError::UnexpectedToken(e)) => {
-            let line_info = LineInfo::from_span(e.span, 1);
-            let err = Error::new(ErrorInfo::new(line_info, file), e);
+        lints.set(&name, level);
+    }
 
-            err.print(context).unwrap();
+    Ok(Options {
+        command,
+        input: input.unwrap_or_else(|| "hello.pop".to_string()),
+        lints,
+        error_format,
+        explain,
+        emit,
+    })
+}
+
+/// Applies the suggestions for the program at `input` until none is left, printing
+/// the changes as a unified diff instead of writing them when `dry_run` is set. Then
+/// reports what is left, exiting with 1 if there are errors.
+fn fix(input: &str, dry_run: bool, lints: &LintLevels, error_format: ErrorFormat) {
+    let Some(program) = fix_program(input, lints) else {
+        eprintln!("error: cannot read `{input}`");
+        std::process::exit(1);
+    };
+    for file in &program.files {
+        if dry_run {
+            print!("{}", unified_diff(&file.path, &file.source, &file.fixed));
+        } else if let Err(err) = std::fs::write(&file.path, &file.fixed) {
+            eprintln!("error: cannot write `{}`: {err}", file.path);
+            std::process::exit(1);
+        } else {
+            eprintln!("fixed {} problem(s) in `{}`", file.applied, file.path);
         }
-        Err(e) => {
-            println!("Failed to parse statement : {:?}", e);
+    }
+    program.errors.emit(program.context, error_format).unwrap();
+    if program.errors.has_errors() {
+        std::process::exit(1);
+    }
+}
+
+/// Formats the file at `input`. With `check`, prints the changes as a unified diff
+/// instead of writing them, and exits with 1 if there are some.
+fn fmt(input: &str, check: bool, error_format: ErrorFormat) {
+    let mut context = Context::new();
+    let mut errors = ErrorTable::new();
+    let Some(file) = format_file(&mut context, input, &mut errors) else {
+        if errors.has_errors() {
+            errors.emit(context, error_format).unwrap();
+        } else {
+            eprintln!("error: cannot read `{input}`");
+        }
+        std::process::exit(1);
+    };
+    if file.formatted == file.source {
+        return;
+    }
+    if check {
+        print!("{}", unified_diff(&file.path, &file.source, &file.formatted));
+        std::process::exit(1);
+    }
+    if let Err(err) = std::fs::write(&file.path, &file.formatted) {
+        eprintln!("error: cannot write `{}`: {err}", file.path);
+        std::process::exit(1);
+    }
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {err}");
+            std::process::exit(1);
         }
+    };
+
+    if let Some(name) = options.explain {
+        let Some(error_code) = registry::parse_code(&name).and_then(registry::lookup) else {
+            eprintln!("error: `{name}` is not a valid error code");
+            std::process::exit(1);
+        };
+        print!("{}", error_code.explanation);
+        return;
+    }
+
+    if let Command::Fmt { check } = options.command {
+        fmt(&options.input, check, options.error_format);
+        return;
+    }
+    if let Command::Fix { dry_run } = options.command {
+        fix(&options.input, dry_run, &options.lints, options.error_format);
+        return;
+    }
+
+    let mut context = Context::new();
+    let mut errors = ErrorTable::with_lints(options.lints);
+
+    let Some(modules) = load_modules(&mut context, &options.input, &mut errors) else {
+        eprintln!("error: cannot read `{}`", options.input);
+        std::process::exit(1);
+    };
+    if let (Some(Emit::AstJson), false) = (options.emit, errors.has_errors()) {
+        // The input file comes after the modules it imports.
+        let ast = modules.last().and_then(|module| context.get_file(module.file)?.ast());
+        println!("{}", serde_json::to_string_pretty(&ast).unwrap());
+        return;
+    }
+    if !errors.has_errors() {
+        check(&context, &modules, &mut errors);
+    }
+
+    errors.emit(context, options.error_format).unwrap();
+    if errors.has_errors() {
+        std::process::exit(1);
+    }
+    if options.error_format == ErrorFormat::Human {
+        println!("Check successfully!");
     }
 }
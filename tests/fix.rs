@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// A directory holding a single `main.pop`, removed when dropped.
+struct Fixture {
+    dir: PathBuf,
+}
+
+impl Fixture {
+    fn new(name: &str, source: &str) -> Fixture {
+        let dir = std::env::temp_dir().join(format!("popper-fix-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.pop"), source).unwrap();
+        Fixture { dir }
+    }
+
+    /// Runs `popper fix` with `args` on `main.pop`, from the fixture directory.
+    fn fix(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_popper_compiler"))
+            .current_dir(&self.dir)
+            .arg("fix")
+            .args(args)
+            .args(["--error-format=json", "main.pop"])
+            .output()
+            .unwrap()
+    }
+
+    fn source(&self) -> String {
+        std::fs::read_to_string(self.dir.join("main.pop")).unwrap()
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Two missing semicolons, a typo and, once the typo is fixed, an unused variable: each
+/// of them stops the parser or the analyzer before the next one is reported.
+const SOURCE: &str = "func main() {\n    let x = 1\n    let y = 2\n    let valeu = 3;\n    let z = vale;\n}\n";
+
+const FIXED: &str = "func main() {\n    let _x = 1;\n    let _y = 2;\n    let valeu = 3;\n    let _z = valeu;\n}\n";
+
+#[test]
+fn test_fix_dry_run() {
+    let fixture = Fixture::new("dry-run", SOURCE);
+    let output = fixture.fix(&["--dry-run"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        concat!(
+            "--- main.pop\n+++ main.pop\n@@ -1,6 +1,6 @@\n func main() {\n",
+            "-    let x = 1\n-    let y = 2\n+    let _x = 1;\n+    let _y = 2;\n",
+            "     let valeu = 3;\n-    let z = vale;\n+    let _z = valeu;\n }\n",
+        )
+    );
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "");
+    assert_eq!(fixture.source(), SOURCE);
+}
+
+#[test]
+fn test_fix_writes_final_source() {
+    let fixture = Fixture::new("write", SOURCE);
+    let output = fixture.fix(&[]);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"");
+    assert_eq!(fixture.source(), FIXED);
+}
+
+#[test]
+fn test_fix_remaining_error() {
+    let fixture = Fixture::new("remaining", "func main() {\n    let x = 1\n    let y = nothing\n}\n");
+    let output = fixture.fix(&["--dry-run"]);
+    // The semicolons are fixed, the unknown symbol without a similar name is reported.
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        concat!(
+            "--- main.pop\n+++ main.pop\n@@ -1,4 +1,4 @@\n func main() {\n",
+            "-    let x = 1\n-    let y = nothing\n+    let x = 1;\n+    let y = nothing;\n }\n",
+        )
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.lines().count(), 1);
+    assert!(stderr.contains(r#""code":"E1001""#));
+}